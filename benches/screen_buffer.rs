@@ -0,0 +1,141 @@
+use std::hint::black_box;
+
+use ascii_assets::{Color, TerminalChar};
+use common_stdx::Rect;
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::{DrawObjectKey, ObjectLifetime};
+use ltrender::error::DrawError;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{BatchDrawInfo, DefaultScreenBuffer};
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, Renderer, ScreenKey};
+
+const SIZE: (u16, u16) = (300, 80);
+const LAYERS: usize = 8;
+
+/// drawer, that throws everything away, so only the buffer gets measured
+#[derive(Debug)]
+struct NullDrawer;
+
+impl CellDrawer for NullDrawer {
+    fn init() -> Self {
+        NullDrawer
+    }
+
+    fn set_string(&mut self, _batch: BatchDrawInfo, _size: (u16, u16)) {}
+
+    fn flush(&mut self) -> Result<(), DrawError> {
+        Ok(())
+    }
+}
+
+type BenchRenderer = Renderer<DefaultScreenBuffer<NullDrawer>, Buffered>;
+
+fn filled_rect(
+    r: &mut BenchRenderer,
+    screen: ScreenKey,
+    layer: usize,
+    rect: Rect<i32>,
+) -> DrawObjectKey {
+    let shade = (layer * 30) as u8;
+    let fill = TerminalChar {
+        chr: (b'a' + layer as u8 % 26) as char,
+        fg_color: Some(Color::rgb(255, shade, 0)),
+        bg_color: Some(Color::rgb(0, 0, shade)),
+    };
+    let obj = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(layer)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(fill))
+                .fill_style(fill)
+                .rect(rect)
+        })
+        .expect("valid rect")
+        .build()
+        .expect("complete object");
+    r.register_drawable(screen, obj).expect("screen exists")
+}
+
+/// renderer with full screen rects stacked on top of each other
+fn stacked(layers: usize) -> (BenchRenderer, Vec<DrawObjectKey>) {
+    let mut r = BenchRenderer::create_renderer(SIZE);
+    let screen = r.create_screen(AreaRect::FullScreen, 0);
+    let keys = (0..layers)
+        .map(|layer| {
+            filled_rect(
+                &mut r,
+                screen,
+                layer,
+                Rect::from_coords(0, 0, SIZE.0 as i32, SIZE.1 as i32),
+            )
+        })
+        .collect();
+    r.render_frame().expect("frame renders");
+    (r, keys)
+}
+
+/// renderer with small rects scattered over the screen
+fn scattered(amount: usize) -> (BenchRenderer, Vec<DrawObjectKey>) {
+    let mut r = BenchRenderer::create_renderer(SIZE);
+    let screen = r.create_screen(AreaRect::FullScreen, 0);
+    let keys = (0..amount)
+        .map(|i| {
+            let x = (i * 7 % (SIZE.0 as usize - 4)) as i32;
+            let y = (i * 3 % (SIZE.1 as usize - 3)) as i32;
+            filled_rect(
+                &mut r,
+                screen,
+                i % LAYERS,
+                Rect::from_coords(x, y, x + 4, y + 3),
+            )
+        })
+        .collect();
+    r.render_frame().expect("frame renders");
+    (r, keys)
+}
+
+fn full_screen_redraw(c: &mut Criterion) {
+    let (mut r, _) = stacked(LAYERS);
+    c.bench_function("full_screen_redraw", |b| {
+        b.iter(|| r.render_frame().expect("frame renders"))
+    });
+    let (mut r, _) = scattered(500);
+    c.bench_function("scattered_redraw", |b| {
+        b.iter(|| r.render_frame().expect("frame renders"))
+    });
+}
+
+fn removal(c: &mut Criterion) {
+    c.bench_function("full_screen_removal", |b| {
+        b.iter_batched(
+            || stacked(LAYERS),
+            |(mut r, keys)| {
+                for key in &keys {
+                    r.explicit_remove_drawable(key).expect("object exists");
+                }
+                r.render_frame().expect("frame renders");
+                black_box(r)
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    c.bench_function("scattered_removal", |b| {
+        b.iter_batched(
+            || scattered(500),
+            |(mut r, keys)| {
+                for key in &keys {
+                    r.explicit_remove_drawable(key).expect("object exists");
+                }
+                r.render_frame().expect("frame renders");
+                black_box(r)
+            },
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+criterion_group!(benches, full_screen_redraw, removal);
+criterion_main!(benches);
@@ -3,7 +3,7 @@ use std::{process::exit, thread};
 use crossterm::event::{KeyCode, MouseButton};
 use log::info;
 use ltrender::{
-    CrosstermEventManager, init_logger, init_terminal,
+    CrosstermEventManager, GenerationalId, init_logger, init_terminal,
     input_handler::{
         hook::InputButton,
         manager::{MouseButtons, MouseMessage, SubscriptionMessage, TargetScreen},
@@ -36,7 +36,10 @@ fn main() {
                         } = sm
                             && let MouseMessage::Pressed(_) = msg
                         {
-                            try_send!(Some(TargetScreen::Screen(1)));
+                            try_send!(Some(TargetScreen::Screen(GenerationalId {
+                                index: 1,
+                                generation: 0,
+                            })));
                         } else {
                             try_send!(None);
                         };
@@ -61,5 +64,4 @@ fn main() {
     while !hook.is_pressed(InputButton::Mouse(MouseButton::Right)) {}
     while hook.is_pressed(InputButton::Mouse(MouseButton::Right)) {}
     handle.join().unwrap();
-    restore_terminal().unwrap();
 }
@@ -71,6 +71,7 @@ fn main() -> Result<(), AppError> {
             creation_time: std::time::Instant::now(),
             drawable: Box::new(menu_drawable),
             layer: 0,
+            alpha: u8::MAX,
             lifetime: ObjectLifetime::ExplicitRemove,
             shaders: Vec::new(),
         },
@@ -82,6 +83,7 @@ fn main() -> Result<(), AppError> {
             break;
         }
     }
-    let _ = restore_terminal();
+    // the menu owns a handle, so dropping ours would not stop the renderer
+    r.shutdown()?;
     Ok(())
 }
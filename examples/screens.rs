@@ -15,7 +15,7 @@ use ltrender::{
         manager::{MouseButtons, TargetScreen},
     },
     rendering::{
-        render_handle::RenderHandle,
+        render_handle::{RenderHandle, WeakRenderHandle},
         render_thread::start_renderer_with_input,
         renderer::{Instant, RenderModeBehavior},
     },
@@ -124,6 +124,7 @@ pub fn main() -> Result<(), AppError> {
     let mut hook = ev_handler.create_hook();
 
     let mut ad_screens = AdjustableScreens::new_uniform_grid(WIDTH, HEIGHT, &mut r)?;
+    let first_screen = ad_screens.screens[0][0];
 
     for screen in &mut ad_screens {
         let builder = DrawObjectBuilder::default();
@@ -156,10 +157,10 @@ pub fn main() -> Result<(), AppError> {
         .layer(200)
         .videostream_drawable(|b| b.position((0, 0)).recv(recv))?
         .add_lifetime(ObjectLifetime::ExplicitRemove)
-        .screen(0)
+        .screen(first_screen)
         .build_and_register(&mut r)?;
     let callback_hook = ev_handler.create_hook();
-    let callback_renderh: RenderHandle<Instant> = r.clone();
+    let callback_renderh: WeakRenderHandle<Instant> = r.downgrade();
     hook.on_mouse_button_press(MouseButtons::Left, move |_| {
         if let TargetScreen::Screen(id) = callback_hook.current_selected_screen() {
             let msg = format!("selected_screen: {}", id);
@@ -172,7 +173,9 @@ pub fn main() -> Result<(), AppError> {
                 size: (data.len() as u16, 1),
                 data,
             });
-            let _ = callback_renderh.render_drawable(text_stream);
+            if let Some(renderh) = callback_renderh.upgrade() {
+                let _ = renderh.render_drawable(text_stream);
+            }
         }
     })?;
     loop {
@@ -192,7 +195,7 @@ pub fn main() -> Result<(), AppError> {
             ad_screens.place_screens_uniformly(&mut r)?;
         }
     }
-    restore_terminal()?;
+    r.shutdown()?;
     info!("returning with ok");
     Ok(())
 }
@@ -17,7 +17,7 @@ use ltrender::draw_object_builder::SpriteDrawableBuilder;
 use ltrender::draw_object_builder::videostream_drawable_builder::make_videostream_drawable;
 use ltrender::drawable_register::ObjectLifetime;
 use ltrender::error::AppError;
-use ltrender::{Renderer, init_logger, init_terminal};
+use ltrender::{Renderer, ScreenKey, init_logger, init_terminal};
 
 use ltrender::terminal_buffer::buffer_and_celldrawer::shaders::Grayscale;
 use ltrender::terminal_buffer::standard_drawables::sprite_drawable::{
@@ -95,6 +95,7 @@ fn make_agent_obj(layer: usize, col: Color) -> DrawObject {
     let fill = TerminalChar::with_fg('#', col);
     DrawObject {
         layer,
+        alpha: u8::MAX,
         shaders: Vec::new(),
         drawable: Box::new(PolygonDrawable {
             points: vec![],
@@ -108,7 +109,7 @@ fn make_agent_obj(layer: usize, col: Color) -> DrawObject {
 
 fn seeded_swarm(
     r: &mut RenderHandle<Buffered>,
-    screen_id: usize,
+    screen_id: ScreenKey,
     n: usize,
     cols: u16,
     rows: u16,
@@ -366,6 +367,7 @@ pub fn main() -> Result<(), AppError> {
         // Simulation ----------------------------------------------
         let (cw, ch) = (cols as f64, rows as f64);
         let t = start.elapsed().as_secs_f64();
+        let mut updates = Vec::with_capacity(agents.len());
 
         for a in &mut agents {
             let fx = ((a.pos.1 * 0.05 + t * 0.3).sin() * 0.8) + (t * 0.1 + a.wobble).cos() * 0.2;
@@ -410,10 +412,18 @@ pub fn main() -> Result<(), AppError> {
             a.pos.0 = wrap(a.pos.0 + a.dir.cos() * step, cw);
             a.pos.1 = wrap(a.pos.1 + a.dir.sin() * step, ch);
 
-            // Push to renderer
             let pts = triangle_points(a.pos.0, a.pos.1, a.dir, a.size);
-            r.replace_drawable_points(a.id, pts)?;
-            r.render_drawable(a.id)?;
+            updates.push((a.id, pts));
+        }
+
+        // Push to renderer, all agents in a single round-trip
+        let results = r.batch(|b| {
+            for (id, pts) in updates {
+                b.replace_drawable_points(id, pts).render_drawable(id);
+            }
+        })?;
+        for res in results {
+            res?;
         }
 
         r.render_drawable(rect_id)?;
@@ -474,7 +484,6 @@ pub fn main() -> Result<(), AppError> {
     }
     r.explicit_remove_drawable(&video_id)?;
 
-    ltrender::restore_terminal()?;
     Ok(())
 }
 
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of time for everything time based inside of the renderer,
+/// like object lifetimes and sprite animations
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// A cheaply clonable handle to a Clock.
+///
+/// Two SharedClocks are equal, if they point to the same clock
+#[derive(Clone, Debug)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new<C: Clock + 'static>(clock: C) -> Self {
+        SharedClock(Arc::new(clock))
+    }
+
+    pub fn from_arc(clock: Arc<dyn Clock>) -> Self {
+        SharedClock(clock)
+    }
+
+    pub fn now(&self) -> Instant {
+        self.0.now()
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        SharedClock::new(SystemClock)
+    }
+}
+
+impl PartialEq for SharedClock {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedClock {}
+
+/// the real time, as reported by std::time::Instant
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock, that only moves forward when told to.
+///
+/// Useful for tests, that need deterministic timings
+#[derive(Debug)]
+pub struct ManualClock {
+    start: Instant,
+    elapsed: Mutex<Duration>,
+    step: Duration,
+}
+
+impl ManualClock {
+    /// create a clock, that advances by `step` on every call to step()
+    pub fn new(step: Duration) -> Self {
+        ManualClock {
+            start: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+            step,
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    pub fn step(&self) {
+        self.advance(self.step);
+    }
+
+    /// time passed since the creation of the clock
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new(Duration::from_millis(16))
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+}
+
+#[derive(Debug)]
+struct ScaledState {
+    // time of the inner clock, when the scale last changed
+    inner_anchor: Instant,
+    // time of this clock, when the scale last changed
+    anchor: Instant,
+    scale: f64,
+    paused: bool,
+}
+
+/// A clock, that runs at a multiple of another clocks speed.
+///
+/// Can be used to pause, slow down or speed up all time based behavior
+#[derive(Debug)]
+pub struct ScaledClock {
+    inner: SharedClock,
+    state: Mutex<ScaledState>,
+}
+
+impl ScaledClock {
+    pub fn new(inner: SharedClock, scale: f64) -> Self {
+        let now = inner.now();
+        ScaledClock {
+            inner,
+            state: Mutex::new(ScaledState {
+                inner_anchor: now,
+                anchor: now,
+                scale: scale.max(0.0),
+                paused: false,
+            }),
+        }
+    }
+
+    /// scale the real time, for example 0.5 for slow motion or 2.0 for fast forward
+    pub fn set_scale(&self, scale: f64) {
+        let mut state = self.state.lock().unwrap();
+        self.reanchor(&mut state);
+        state.scale = scale.max(0.0);
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.state.lock().unwrap().scale
+    }
+
+    pub fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        self.reanchor(&mut state);
+        state.paused = true;
+    }
+
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        self.reanchor(&mut state);
+        state.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().paused
+    }
+
+    fn current(&self, state: &ScaledState, inner_now: Instant) -> Instant {
+        if state.paused {
+            return state.anchor;
+        }
+        let passed = inner_now.saturating_duration_since(state.inner_anchor);
+        state.anchor + passed.mul_f64(state.scale)
+    }
+
+    fn reanchor(&self, state: &mut ScaledState) {
+        let inner_now = self.inner.now();
+        state.anchor = self.current(state, inner_now);
+        state.inner_anchor = inner_now;
+    }
+}
+
+impl Clock for ScaledClock {
+    fn now(&self) -> Instant {
+        let state = self.state.lock().unwrap();
+        self.current(&state, self.inner.now())
+    }
+}
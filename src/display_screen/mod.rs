@@ -1,13 +1,15 @@
 use std::fmt;
 
 use crate::{
-    DrawError, DrawObjectKey, DrawObjectLibrary, ObjectId, ScreenBuffer, SpriteRegistry,
+    DrawError, DrawObjectKey, DrawObjectLibrary, GenerationalId, ObjectId, ScreenBuffer,
+    SpriteRegistry,
     error::AppError,
     input_handler::manager::{SubscriptionMessage, TargetScreen},
+    rendering::panic_isolation::catch_panic,
     terminal_buffer::CellDrawer,
 };
 use common_stdx::{Point, Rect};
-pub type ScreenKey = usize;
+pub type ScreenKey = GenerationalId;
 
 pub mod area_rect;
 pub use area_rect::{AreaPoint, AreaRect};
@@ -55,25 +57,36 @@ impl Screen {
         obj_library: &mut DrawObjectLibrary,
     ) -> Result<(), DrawError> {
         for drawable in &self.draw_objects {
-            if let Some(d_o) = obj_library.get_mut(&DrawObjectKey {
+            let key = DrawObjectKey {
                 screen_id: self.id,
                 object_id: *drawable,
-            }) {
+            };
+            if obj_library.is_quarantined(&key) {
+                continue;
+            }
+            if let Some(d_o) = obj_library.get_mut(&key) {
                 let d = d_o.drawable.as_mut();
-                match m {
-                    SubscriptionMessage::Key { msg, screen } => {
-                        if screen.targeting(self.id) {
-                            d.on_key_press(*msg)?;
+                let res = catch_panic(|| {
+                    match m {
+                        SubscriptionMessage::Key { msg, screen } => {
+                            if screen.targeting(self.id) {
+                                d.on_key_press(*msg)?;
+                            }
+                            d.on_any_key_press(*msg, *screen)?;
                         }
-                        d.on_any_key_press(*msg, *screen)?;
-                    }
-                    SubscriptionMessage::Mouse { msg, screen } => {
-                        if screen.targeting(self.id) {
-                            d.on_mousekey_press(*msg)?;
+                        SubscriptionMessage::Mouse { msg, screen } => {
+                            if screen.targeting(self.id) {
+                                d.on_mousekey_press(*msg)?;
+                            }
+                            d.on_any_mousekey_press(*msg, *screen)?;
                         }
-                        d.on_any_mousekey_press(*msg, *screen)?;
+                        _ => {}
                     }
-                    _ => {}
+                    Ok::<(), DrawError>(())
+                });
+                match res {
+                    Ok(res) => res?,
+                    Err(message) => obj_library.quarantine(key, message),
                 }
             }
         }
@@ -96,13 +109,21 @@ impl Screen {
             (callback)();
         }
         for object_id in &self.draw_objects {
-            if let Some(obj) = obj_library.get_mut(&DrawObjectKey {
+            let key = DrawObjectKey {
                 screen_id: self.id,
                 object_id: *object_id,
-            }) {
+            };
+            if obj_library.is_quarantined(&key) {
+                continue;
+            }
+            if let Some(obj) = obj_library.get_mut(&key) {
                 // todo: give objects global selection infos too?
-                obj.drawable
-                    .on_screen_select(TargetScreen::Screen(self.id))?;
+                let res =
+                    catch_panic(|| obj.drawable.on_screen_select(TargetScreen::Screen(self.id)));
+                match res {
+                    Ok(res) => res?,
+                    Err(message) => obj_library.quarantine(key, message),
+                }
             }
         }
         Ok(())
@@ -122,14 +143,21 @@ impl Screen {
                 object_id: *object_id,
             };
 
+            if obj_library.is_quarantined(&key) {
+                continue;
+            }
             let obj = match obj_library.get_mut(&key) {
                 Some(obj) => obj,
                 None => continue,
             };
 
-            let bdc = match obj.drawable.draw(sprites) {
-                Ok(bdc) => bdc,
-                Err(_) => continue,
+            let bdc = match catch_panic(|| obj.drawable.draw(sprites)) {
+                Ok(Ok(bdc)) => bdc,
+                Ok(Err(_)) => continue,
+                Err(message) => {
+                    obj_library.quarantine(key, message);
+                    continue;
+                }
             };
 
             let bbox = bdc.get_bounding_box().normalized();
@@ -195,18 +223,28 @@ impl Screen {
             return Ok(());
         }
 
-        let opt_obj = obj_library.get_mut(&DrawObjectKey {
+        let key = DrawObjectKey {
             screen_id: self.id,
             object_id,
-        });
-        if let Some(obj) = opt_obj {
+        };
+        if obj_library.is_quarantined(&key) {
+            return Ok(());
+        }
+        if let Some(obj) = obj_library.all_objects.get_mut(&key) {
             let rect = &self.area.area_to_rect(&self.terminal_size);
-            screen_buffer.add_to_buffer(obj, object_id, self.layer, rect, sprites)?;
-        } else {
-            return Err(DrawError::DrawableHandleNotFound {
-                screen_id: self.id,
-                obj_id: object_id,
+            let res = catch_panic(|| {
+                screen_buffer.add_to_buffer(obj, object_id, self.layer, rect, sprites)
             });
+            match res {
+                Ok(res) => res?,
+                Err(message) => {
+                    // remove whatever has been drawn before the panic
+                    screen_buffer.remove_from_buffer(object_id);
+                    obj_library.quarantine(key, message);
+                }
+            }
+        } else {
+            return Err(obj_library.missing_key_error(&key));
         };
 
         Ok(())
@@ -222,25 +260,19 @@ impl Screen {
         object_id: ObjectId,
         screen_buffer: &mut B,
         obj_library: &mut DrawObjectLibrary,
-        sprites: &SpriteRegistry,
     ) -> Result<(), DrawError>
     where
         B: ScreenBuffer,
         B::Drawer: CellDrawer,
     {
-        let opt_obj = obj_library.get_mut(&DrawObjectKey {
+        let key = DrawObjectKey {
             screen_id: self.id,
             object_id,
-        });
-        if let Some(obj) = opt_obj {
-            let rect = &self.area.area_to_rect(&self.terminal_size);
-            screen_buffer.remove_from_buffer(obj, object_id, sprites, rect);
-        } else {
-            return Err(DrawError::DrawableHandleNotFound {
-                screen_id: self.id,
-                obj_id: object_id,
-            });
         };
+        if !obj_library.all_objects.contains_key(&key) {
+            return Err(obj_library.missing_key_error(&key));
+        }
+        screen_buffer.remove_from_buffer(object_id);
         Ok(())
     }
 
@@ -248,7 +280,6 @@ impl Screen {
         &mut self,
         screen_buffer: &mut B,
         obj_library: &mut DrawObjectLibrary,
-        sprites: &SpriteRegistry,
     ) -> Result<(), DrawError>
     where
         B: ScreenBuffer,
@@ -256,7 +287,7 @@ impl Screen {
     {
         let obj_ids: Vec<_> = self.draw_objects.to_vec();
         for obj_id in obj_ids {
-            self.remove_drawable(obj_id, screen_buffer, obj_library, sprites)?;
+            self.remove_drawable(obj_id, screen_buffer, obj_library)?;
         }
 
         Ok(())
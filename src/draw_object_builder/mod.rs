@@ -5,7 +5,7 @@ use crate::{
     drawable_register::ObjectLifetime,
     error::{AppError, DrawObjectBuilderError},
     rendering::{render_handle::RenderHandle, renderer::RenderModeBehavior},
-    terminal_buffer::buffer_and_celldrawer::Shader,
+    terminal_buffer::{buffer_and_celldrawer::Shader, compositing::OPAQUE},
 };
 pub mod sprite_drawable_builder;
 pub use sprite_drawable_builder::SpriteDrawableBuilder;
@@ -73,12 +73,14 @@ pub struct DrawObjectBuilder {
     drawable: Option<Box<dyn Drawable>>,
     screen_id: Option<ScreenKey>,
     lt: Option<ObjectLifetime>,
+    alpha: Option<u8>,
 }
 
 impl DrawObjectBuilder {
     handle_field!(layer, layer, usize);
-    handle_field!(screen, screen_id, usize);
+    handle_field!(screen, screen_id, ScreenKey);
     handle_field!(add_lifetime, lt, ObjectLifetime);
+    handle_field!(alpha, alpha, u8);
     pub fn shader<T>(mut self, shader: T) -> Self
     where
         T: Shader + 'static,
@@ -125,6 +127,7 @@ impl DrawObjectBuilder {
         Ok(DrawObject {
             lifetime,
             layer,
+            alpha: self.alpha.unwrap_or(OPAQUE),
             drawable,
             shaders: self.shaders.clone(),
             creation_time: Instant::now(),
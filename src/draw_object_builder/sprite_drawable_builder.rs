@@ -1,7 +1,7 @@
 use common_stdx::Point;
 
 use crate::{
-    Drawable, SpriteDrawable, SpriteId,
+    Drawable, SharedClock, SpriteDrawable, SpriteId,
     error::DrawObjectBuilderError,
     terminal_buffer::standard_drawables::sprite_drawable::{AnimationInfo, FrameIdent},
 };
@@ -33,15 +33,17 @@ impl SpriteDrawableBuilder {
     }
 
     pub fn build(self) -> Result<Box<dyn Drawable>, DrawObjectBuilderError> {
+        let clock = SharedClock::default();
         Ok(Box::new(SpriteDrawable {
             position: self.position.unwrap_or(Point { x: 0, y: 0 }),
             sprite_id: self
                 .sprite_id
                 .ok_or(DrawObjectBuilderError::FailedToBuildSpriteObject())?,
-            last_state_change: std::time::Instant::now(),
+            last_state_change: clock.now(),
             animation_type: self.animation_type.unwrap_or(AnimationInfo::Image {
                 frame: FrameIdent::FirstFrame,
             }),
+            clock,
         }))
     }
 }
@@ -1,6 +1,9 @@
-use crate::{Drawable, ObjectId, ScreenKey, terminal_buffer::buffer_and_celldrawer::Shader};
+use crate::{
+    DrawError, Drawable, GenerationalIdAllocator, ObjectId, ScreenKey,
+    terminal_buffer::buffer_and_celldrawer::Shader,
+};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     time::{Duration, Instant},
 };
 
@@ -23,6 +26,8 @@ pub struct DrawObject {
     pub lifetime: ObjectLifetime,
     pub creation_time: Instant,
     pub layer: usize,
+    /// opacity used when compositing with the layers below, 255 is fully opaque
+    pub alpha: u8,
     pub shaders: Vec<Box<dyn Shader>>,
     pub drawable: Box<dyn Drawable + 'static>,
 }
@@ -30,17 +35,42 @@ pub struct DrawObject {
 #[derive(Debug, Default)]
 pub struct DrawObjectLibrary {
     pub all_objects: HashMap<DrawObjectKey, DrawObject>,
+    ids: GenerationalIdAllocator,
+    /// objects, whose drawable panicked, they are neither drawn nor receive input
+    quarantined: HashSet<DrawObjectKey>,
+    /// panics, that have not been reported yet
+    panics: Vec<(DrawObjectKey, String)>,
 }
 
 impl DrawObjectLibrary {
     pub fn new() -> Self {
         Self {
             all_objects: HashMap::new(),
+            ids: GenerationalIdAllocator::new(),
+            quarantined: HashSet::new(),
+            panics: Vec::new(),
         }
     }
 
+    /// stop drawing the object and passing input to it, after its drawable panicked
+    pub fn quarantine(&mut self, key: DrawObjectKey, message: String) {
+        log::error!("object {:?} panicked: {}", key, message);
+        if self.quarantined.insert(key) {
+            self.panics.push((key, message));
+        }
+    }
+
+    pub fn is_quarantined(&self, key: &DrawObjectKey) -> bool {
+        self.quarantined.contains(key)
+    }
+
+    /// the objects quarantined since the last call, with their panic messages
+    pub fn take_panics(&mut self) -> Vec<(DrawObjectKey, String)> {
+        std::mem::take(&mut self.panics)
+    }
+
     pub fn add_obj(&mut self, screen_id: ScreenKey, object: DrawObject) -> ObjectId {
-        let new_id = self.generate_drawable_id();
+        let new_id = self.ids.allocate();
         self.all_objects.insert(
             DrawObjectKey {
                 screen_id,
@@ -51,10 +81,17 @@ impl DrawObjectLibrary {
         new_id
     }
 
+    /// replace the object, a quarantined one gets another chance
     pub fn update_drawable(&mut self, id: DrawObjectKey, new_object: DrawObject) {
+        self.release(&id);
         self.all_objects.insert(id, new_object);
     }
 
+    /// lift the quarantine of the object, once its drawable has been replaced
+    pub fn release(&mut self, key: &DrawObjectKey) {
+        self.quarantined.remove(key);
+    }
+
     pub fn find_drawable(&self, key: &DrawObjectKey) -> Option<&DrawObject> {
         self.all_objects.get(key)
     }
@@ -70,7 +107,47 @@ impl DrawObjectLibrary {
             .map(|(k, _)| &k.screen_id)
     }
 
-    fn generate_drawable_id(&self) -> ObjectId {
-        self.all_objects.len()
+    /// Remove an object from the library for good.
+    /// Every copy of its key is stale afterwards
+    pub fn remove_obj(&mut self, key: &DrawObjectKey) -> Option<DrawObject> {
+        let obj = self.all_objects.remove(key)?;
+        self.quarantined.remove(key);
+        self.ids.free(key.object_id);
+        Some(obj)
+    }
+
+    /// Remove every object of the screen from the library.
+    /// returns the ids of the removed objects
+    pub fn remove_screen_objs(&mut self, screen_id: ScreenKey) -> Vec<ObjectId> {
+        let keys: Vec<DrawObjectKey> = self
+            .all_objects
+            .keys()
+            .filter(|key| key.screen_id == screen_id)
+            .copied()
+            .collect();
+        for key in &keys {
+            self.remove_obj(key);
+        }
+        keys.into_iter().map(|key| key.object_id).collect()
+    }
+
+    /// returns true, if the key belongs to an object, that has been removed from the library
+    pub fn is_stale(&self, key: &DrawObjectKey) -> bool {
+        self.ids.is_stale(key.object_id)
+    }
+
+    /// the error to return, when an object could not be found for the given key
+    pub fn missing_key_error(&self, key: &DrawObjectKey) -> DrawError {
+        if self.is_stale(key) {
+            DrawError::StaleDrawableHandle {
+                screen_id: key.screen_id,
+                obj_id: key.object_id,
+            }
+        } else {
+            DrawError::DrawableHandleNotFound {
+                screen_id: key.screen_id,
+                obj_id: key.object_id,
+            }
+        }
     }
 }
@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use ascii_assets::{Color, TerminalChar};
 use common_stdx::{Point, Rect};
 
-use crate::BasicDraw;
+use crate::{
+    BasicDraw, CellAttributes,
+    terminal_buffer::grapheme::{Grapheme, graphemes},
+};
 
 #[derive(Debug, Clone)]
 pub struct VerticalBarGraphConfig {
@@ -73,6 +76,10 @@ impl Default for LineGraphConfig {
 #[derive(Debug, Default)]
 pub struct BasicDrawCreator {
     pub draws: HashMap<Point<i32>, TerminalChar>,
+    /// attributes of the draws, positions without an entry have none
+    pub attributes: HashMap<Point<i32>, CellAttributes>,
+    /// grapheme clusters of the draws, that consist of more than their char
+    pub clusters: HashMap<Point<i32>, Grapheme>,
 }
 
 impl BasicDrawCreator {
@@ -82,14 +89,66 @@ impl BasicDrawCreator {
     pub fn new_with_capacity(cap: usize) -> Self {
         Self {
             draws: HashMap::with_capacity(cap),
+            attributes: HashMap::new(),
+            clusters: HashMap::new(),
         }
     }
 
     /// Draw a single character at `pos`.
     ///
-    /// If a character already exists at this position, it is replaced.
+    /// If a character already exists at this position, it is replaced, together with its attributes.
     pub fn draw_char(&mut self, pos: impl Into<Point<i32>>, chr: TerminalChar) {
-        self.draws.insert(pos.into(), chr);
+        let pos = pos.into();
+        self.attributes.remove(&pos);
+        self.clusters.remove(&pos);
+        self.draws.insert(pos, chr);
+    }
+
+    /// Draw a single character with attributes at `pos`.
+    pub fn draw_styled_char(
+        &mut self,
+        pos: impl Into<Point<i32>>,
+        chr: TerminalChar,
+        attrs: CellAttributes,
+    ) {
+        let pos = pos.into();
+        self.clusters.remove(&pos);
+        self.draws.insert(pos, chr);
+        self.set_attributes(pos, attrs);
+    }
+
+    /// Draw a grapheme cluster at `pos`, using the colors of `template`.
+    ///
+    /// Returns the amount of cells the cluster takes up. Wide clusters also cover the cell to the right,
+    /// which is hidden by the screen buffer
+    pub fn draw_grapheme(
+        &mut self,
+        pos: impl Into<Point<i32>>,
+        cluster: &str,
+        template: TerminalChar,
+        attrs: CellAttributes,
+    ) -> usize {
+        let pos = pos.into();
+        let grapheme = Grapheme::new(cluster);
+        let chr = TerminalChar {
+            chr: grapheme.base(),
+            ..template
+        };
+        self.draw_styled_char(pos, chr, attrs);
+        if cluster.chars().nth(1).is_some() {
+            self.clusters.insert(pos, grapheme);
+        }
+        grapheme.width()
+    }
+
+    /// Replace the attributes of the character at `pos`
+    pub fn set_attributes(&mut self, pos: impl Into<Point<i32>>, attrs: CellAttributes) {
+        let pos = pos.into();
+        if attrs.is_empty() {
+            self.attributes.remove(&pos);
+        } else {
+            self.attributes.insert(pos, attrs);
+        }
     }
 
     /// Draw a straight line from `p1` to `p2` (inclusive) using Bresenham’s algorithm.
@@ -134,26 +193,30 @@ impl BasicDrawCreator {
     }
 
     /// Draw a horizontal text string starting at `pos`.
-    /// Each character advances x by 1. Newlines are interpreted (advances y and resets x to start.x).
+    /// Each grapheme cluster advances x by its display width. Newlines are interpreted (advances y and resets x to start.x).
     /// If `fg` is `Some(Color)` it will be used
     pub fn draw_text(&mut self, pos: impl Into<Point<i32>>, text: &str, fg: Option<Color>) {
         let start = pos.into();
         let mut x = start.x;
         let mut y = start.y;
 
-        for ch in text.chars() {
-            match ch {
-                '\n' => {
+        let mut template = TerminalChar::from_char(' ');
+        if let Some(col) = fg {
+            template = template.set_fg(col);
+        }
+        for (_, cluster, _) in graphemes(text) {
+            match cluster {
+                "\n" | "\r\n" => {
                     y += 1;
                     x = start.x;
                 }
                 _ => {
-                    let mut t = TerminalChar::from_char(ch);
-                    if let Some(col) = fg {
-                        t = t.set_fg(col);
-                    }
-                    self.draws.insert(Point::new(x, y), t);
-                    x += 1;
+                    x += self.draw_grapheme(
+                        Point::new(x, y),
+                        cluster,
+                        template,
+                        CellAttributes::NONE,
+                    ) as i32;
                 }
             }
         }
@@ -345,14 +408,22 @@ impl BasicDrawCreator {
     ///
     /// Incoming characters overwrite existing positions.
     pub fn merge_creator(&mut self, to_merge: BasicDrawCreator) {
-        self.draws.extend(to_merge.draws);
+        self.merge_creator_offset(to_merge, Point { x: 0, y: 0 });
     }
 
     /// Merge another creator, offsetting all positions by `offset`.
     pub fn merge_creator_offset(&mut self, to_merge: BasicDrawCreator, offset: Point<i32>) {
         for (pos, chr) in to_merge.draws {
+            self.attributes.remove(&(pos + offset));
+            self.clusters.remove(&(pos + offset));
             self.draws.insert(pos + offset, chr);
         }
+        for (pos, attrs) in to_merge.attributes {
+            self.attributes.insert(pos + offset, attrs);
+        }
+        for (pos, cluster) in to_merge.clusters {
+            self.clusters.insert(pos + offset, cluster);
+        }
     }
 
     /// Translate all draws so the bounding box top-left aligns with `origin`.
@@ -364,15 +435,31 @@ impl BasicDrawCreator {
         for (pos, chr) in std::mem::take(&mut self.draws) {
             new_draws.insert(pos + offset, chr);
         }
-
         self.draws = new_draws;
+
+        self.attributes = std::mem::take(&mut self.attributes)
+            .into_iter()
+            .map(|(pos, attrs)| (pos + offset, attrs))
+            .collect();
+        self.clusters = std::mem::take(&mut self.clusters)
+            .into_iter()
+            .map(|(pos, cluster)| (pos + offset, cluster))
+            .collect();
     }
 
     /// Consume the creator and return all draws as a `Vec<BasicDraw>`.
     pub fn dump_draws(&mut self) -> Vec<BasicDraw> {
+        let mut attributes = std::mem::take(&mut self.attributes);
+        let mut clusters = std::mem::take(&mut self.clusters);
         std::mem::take(&mut self.draws)
             .into_iter()
-            .map(|(pos, chr)| BasicDraw { pos, chr })
+            .map(|(pos, chr)| BasicDraw {
+                pos,
+                chr,
+                attrs: attributes.remove(&pos).unwrap_or_default(),
+                // the char could have been replaced without clearing its cluster
+                cluster: clusters.remove(&pos).filter(|c| c.base() == chr.chr),
+            })
             .collect()
     }
 }
@@ -1,13 +1,15 @@
 use crate::{
-    DrawError, SpriteRegistry,
+    CellAttributes, DrawError, SharedClock, SpriteRegistry,
     drawable_traits::basic_draw_creator::BasicDrawCreator,
     input_handler::manager::{KeyMessage, MouseMessage, TargetScreen},
+    terminal_buffer::grapheme::Grapheme,
     update_interval_handler::UpdateIntervalCreator,
 };
 
 use ascii_assets::TerminalChar;
 use common_stdx::{Point, Rect};
 use log::warn;
+use std::any::Any;
 
 pub mod basic_draw_creator;
 
@@ -15,9 +17,61 @@ pub mod basic_draw_creator;
 pub struct BasicDraw {
     pub pos: Point<i32>,
     pub chr: TerminalChar,
+    pub attrs: CellAttributes,
+    /// the whole grapheme cluster, if chr is followed by combining chars
+    pub cluster: Option<Grapheme>,
 }
 
-pub trait Drawable: std::fmt::Debug + Send {
+/// Gives access to the concrete type behind a `dyn Drawable`.
+///
+/// Implemented for every sized type, so drawables never need to implement it manually
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    /// name of the concrete type, used for error messages
+    fn drawable_type_name(&self) -> &'static str;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn drawable_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+/// A mutation of a drawable, that gets shipped to the render thread
+pub type DrawableUpdate =
+    Box<dyn FnOnce(&mut (dyn Drawable + 'static)) -> Result<(), DrawError> + Send>;
+
+/// Wrap a closure, that mutates a concrete drawable type, into a DrawableUpdate.
+///
+/// The update fails with DrawError::WrongDrawableType, if the drawable is not of type T
+pub fn typed_update<T, F>(update: F) -> DrawableUpdate
+where
+    T: Drawable,
+    F: FnOnce(&mut T) + Send + 'static,
+{
+    Box::new(move |drawable: &mut (dyn Drawable + 'static)| {
+        let found = (*drawable).drawable_type_name();
+        match drawable.as_any_mut().downcast_mut::<T>() {
+            Some(d) => {
+                update(d);
+                Ok(())
+            }
+            None => Err(DrawError::WrongDrawableType {
+                expected: std::any::type_name::<T>(),
+                found,
+            }),
+        }
+    })
+}
+
+pub trait Drawable: std::fmt::Debug + Send + AsAny {
     /// The Output of this function will be used to render to the terminal
     fn draw(&mut self, sprites: &SpriteRegistry) -> Result<BasicDrawCreator, DrawError>;
 
@@ -77,6 +131,12 @@ pub trait Drawable: std::fmt::Debug + Send {
         Ok(())
     }
 
+    /// gets called, when the drawable gets registered, or the renderers clock changes.
+    /// Time based drawables should read the time from this clock, instead of std::time::Instant
+    fn attach_clock(&mut self, clock: SharedClock) {
+        let _ = clock;
+    }
+
     fn get_top_left(&mut self) -> Option<Point<i32>> {
         if self.as_double_pointed_mut().is_some() {
             Some(self.as_double_pointed_mut().unwrap().start())
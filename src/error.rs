@@ -1,7 +1,7 @@
 use thiserror::Error;
 
 use crate::{
-    SpriteId,
+    ObjectId, ScreenKey, SpriteId,
     input_handler::manager::{EventManagerCommand, SubscriptionMessage},
 };
 use std::fmt::Debug;
@@ -20,6 +20,9 @@ pub enum AppError {
     #[error(transparent)]
     InputCommunication(#[from] EventCommunicationError),
 
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
@@ -27,6 +30,55 @@ pub enum AppError {
     SendError,
     #[error("failed to receive renderer Response")]
     RecvError,
+    #[error("the render thread panicked: {0}")]
+    RenderThreadPanicked(String),
+}
+
+/// The part of the render loop, that produced a RenderError
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderPhase {
+    /// removing objects, that only live for a single frame
+    FramebasedCleanup,
+    /// removing objects, whose lifetime has ended
+    LifetimeCheck,
+    ScreenSelection,
+    Input,
+    /// flushing the cell drawer
+    Drawer,
+    /// an object panicked and will not be drawn or receive input anymore
+    Quarantine,
+}
+
+impl std::fmt::Display for RenderPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let phase = match self {
+            RenderPhase::FramebasedCleanup => "removing frame based objects",
+            RenderPhase::LifetimeCheck => "checking object lifetimes",
+            RenderPhase::ScreenSelection => "selecting a screen",
+            RenderPhase::Input => "handling input",
+            RenderPhase::Drawer => "drawing",
+            RenderPhase::Quarantine => "isolating an object",
+        };
+        f.write_str(phase)
+    }
+}
+
+/// An error of the render thread, that no caller has been waiting for
+#[derive(Debug, Error)]
+#[error("error while {phase}: {error}")]
+pub struct RenderError {
+    pub phase: RenderPhase,
+    #[source]
+    pub error: AppError,
+}
+
+/// Whether the render thread keeps running after a RenderError
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    #[default]
+    Continue,
+    /// stop the render thread after reporting the error, like a shutdown
+    Stop,
 }
 
 #[derive(Debug, Error)]
@@ -58,6 +110,26 @@ pub enum FileError {
     VideoFrameNotFound { video_path: String, frame_id: usize },
 }
 
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed parsing snapshot at line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("Snapshot {path} does not match the frame:\n{report}")]
+    Mismatch { path: String, report: String },
+
+    #[error(
+        "Snapshot {path} does not exist, set LTRENDER_UPDATE_SNAPSHOTS=1 to create it. Found frame:\n{snapshot}"
+    )]
+    Missing { path: String, snapshot: String },
+
+    #[error("Frame uses {found} colors, but a snapshot can only hold {max}")]
+    TooManyColors { found: usize, max: usize },
+}
+
 #[derive(Debug, Error)]
 pub enum DrawError {
     #[error("Wrong drawable type: expected `{expected}`, found `{found}`")]
@@ -67,10 +139,29 @@ pub enum DrawError {
     },
 
     #[error("Screen with id {0} not found")]
-    DisplayKeyNotFound(usize),
+    DisplayKeyNotFound(ScreenKey),
+
+    #[error("Screen with id {0} has been destroyed")]
+    StaleScreenKey(ScreenKey),
 
     #[error("Object {obj_id} not found on screen {screen_id}")]
-    DrawableHandleNotFound { screen_id: usize, obj_id: usize },
+    DrawableHandleNotFound {
+        screen_id: ScreenKey,
+        obj_id: ObjectId,
+    },
+
+    #[error("Object {obj_id} on screen {screen_id} has been destroyed")]
+    StaleDrawableHandle {
+        screen_id: ScreenKey,
+        obj_id: ObjectId,
+    },
+
+    #[error("Object {obj_id} on screen {screen_id} panicked and has been quarantined: {message}")]
+    DrawablePanicked {
+        screen_id: ScreenKey,
+        obj_id: ObjectId,
+        message: String,
+    },
 
     #[error("Sprite {0} not found")]
     SpriteNotFound(SpriteId),
@@ -78,6 +169,12 @@ pub enum DrawError {
     #[error("frame {1} of Sprite {0} not found")]
     SpriteFrameNotFound(SpriteId, u16),
 
+    #[error("No recording has been started")]
+    NotRecording,
+
+    #[error("The recording does not contain any frames")]
+    EmptyRecording,
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
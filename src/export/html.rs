@@ -0,0 +1,79 @@
+use std::fmt::Write;
+
+use crate::export::{ExportOptions, cell_style, escape_markup, hex, text_decoration};
+use crate::terminal_buffer::CellAttributes;
+use crate::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation;
+
+/// Export the content as a standalone html page.
+///
+/// Every row becomes a line inside of a `<pre>`, runs of cells with the same style share one `<span>`
+pub fn export_html(content: &TerminalContentInformation, options: &ExportOptions) -> String {
+    let (width, height) = (content.size.0 as usize, content.size.1 as usize);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>\npre.ltrender {{ font-family: {}; font-size: {}px; line-height: 1.2; \
+         color: {}; background-color: {}; padding: 0.5em; display: inline-block; }}\n</style>\n\
+         </head>\n<body>\n<pre class=\"ltrender\">",
+        escape_markup(&options.title),
+        escape_markup(&options.font_family),
+        options.font_size,
+        hex(options.default_fg),
+        hex(options.default_bg),
+    );
+
+    for y in 0..height {
+        let mut x = 0;
+        while x < width {
+            let (_, fg, bg, attrs) = cell_style(content, y * width + x, options);
+            let mut text = String::new();
+            while x < width {
+                let (chr, cell_fg, cell_bg, cell_attrs) =
+                    cell_style(content, y * width + x, options);
+                if (cell_fg, cell_bg, cell_attrs) != (fg, bg, attrs) {
+                    break;
+                }
+                chr.push_to(&mut text);
+                // the continuation cell of a wide char is covered by the char itself
+                x += chr.width();
+            }
+
+            let mut style = String::new();
+            if let Some(fg) = fg {
+                let _ = write!(style, "color:{};", hex(fg));
+            }
+            if let Some(bg) = bg {
+                let _ = write!(style, "background-color:{};", hex(bg));
+            }
+            if attrs.contains(CellAttributes::BOLD) {
+                style.push_str("font-weight:bold;");
+            }
+            if attrs.contains(CellAttributes::DIM) {
+                style.push_str("opacity:0.6;");
+            }
+            if attrs.contains(CellAttributes::ITALIC) {
+                style.push_str("font-style:italic;");
+            }
+            if let Some(decoration) = text_decoration(attrs) {
+                let _ = write!(style, "text-decoration:{};", decoration);
+            }
+            if style.is_empty() {
+                out.push_str(&escape_markup(&text));
+            } else {
+                let _ = write!(
+                    out,
+                    "<span style=\"{}\">{}</span>",
+                    style,
+                    escape_markup(&text)
+                );
+            }
+        }
+        if y + 1 < height {
+            out.push('\n');
+        }
+    }
+
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
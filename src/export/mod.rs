@@ -0,0 +1,127 @@
+pub mod html;
+pub use html::export_html;
+
+pub mod svg;
+pub use svg::export_svg;
+
+use ascii_assets::{Color, TerminalChar};
+
+use crate::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation;
+use crate::terminal_buffer::{CellAttributes, StyledChar};
+
+/// Settings shared by all exporters
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub title: String,
+    pub font_family: String,
+    pub font_size: u16,
+    /// used for cells without a color, or with a reset color
+    pub default_fg: (u8, u8, u8),
+    pub default_bg: (u8, u8, u8),
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            title: String::from("ltrender export"),
+            font_family: String::from("ui-monospace, Menlo, Consolas, monospace"),
+            font_size: 14,
+            default_fg: (229, 229, 229),
+            default_bg: (0, 0, 0),
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn font_family(mut self, font_family: impl Into<String>) -> Self {
+        self.font_family = font_family.into();
+        self
+    }
+
+    pub fn font_size(mut self, font_size: u16) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn default_fg(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.default_fg = rgb;
+        self
+    }
+
+    pub fn default_bg(mut self, rgb: (u8, u8, u8)) -> Self {
+        self.default_bg = rgb;
+        self
+    }
+}
+
+pub(crate) type Rgb = (u8, u8, u8);
+
+/// The char, colors and attributes of a cell, where None is used for empty cells and reset colors.
+/// Reversed cells already have their colors swapped
+pub(crate) fn cell_style(
+    content: &TerminalContentInformation,
+    idx: usize,
+    options: &ExportOptions,
+) -> (StyledChar, Option<Rgb>, Option<Rgb>, CellAttributes) {
+    let chr = content
+        .styled_char(idx)
+        .unwrap_or(StyledChar::from(TerminalChar {
+            chr: ' ',
+            fg_color: None,
+            bg_color: None,
+        }));
+    let (fg, bg, attrs) = (rgb(chr.chr.fg_color), rgb(chr.chr.bg_color), chr.attrs);
+    if attrs.contains(CellAttributes::REVERSE) {
+        (
+            chr,
+            Some(bg.unwrap_or(options.default_bg)),
+            Some(fg.unwrap_or(options.default_fg)),
+            attrs,
+        )
+    } else {
+        (chr, fg, bg, attrs)
+    }
+}
+
+/// the css text-decoration for the attributes, if any
+pub(crate) fn text_decoration(attrs: CellAttributes) -> Option<&'static str> {
+    match (
+        attrs.contains(CellAttributes::UNDERLINE),
+        attrs.contains(CellAttributes::STRIKETHROUGH),
+    ) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+fn rgb(color: Option<Color>) -> Option<Rgb> {
+    color.filter(|c| !c.reset).map(|c| c.rgb)
+}
+
+pub(crate) fn hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// escape text for html as well as xml
+pub(crate) fn escape_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use crate::export::{ExportOptions, cell_style, escape_markup, hex, text_decoration};
+use crate::terminal_buffer::CellAttributes;
+use crate::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation;
+
+/// width of a cell relative to the font size
+const CELL_WIDTH: f32 = 0.6;
+/// height of a cell relative to the font size
+const CELL_HEIGHT: f32 = 1.2;
+
+/// Export the content as a standalone svg.
+///
+/// Backgrounds become rects and every run of text with the same style becomes a `<text>`,
+/// stretched to the width of its cells, so the grid stays aligned with any monospace font
+pub fn export_svg(content: &TerminalContentInformation, options: &ExportOptions) -> String {
+    let (width, height) = (content.size.0 as usize, content.size.1 as usize);
+    let cell_w = options.font_size as f32 * CELL_WIDTH;
+    let cell_h = options.font_size as f32 * CELL_HEIGHT;
+    let cell = |x: usize, y: usize| cell_style(content, y * width + x, options);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"{}\" font-size=\"{}\">",
+        escape_markup(&options.font_family),
+        options.font_size,
+        w = width as f32 * cell_w,
+        h = height as f32 * cell_h,
+    );
+    let _ = writeln!(out, "<title>{}</title>", escape_markup(&options.title));
+    let _ = writeln!(
+        out,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        hex(options.default_bg)
+    );
+
+    for y in 0..height {
+        // backgrounds
+        let mut x = 0;
+        while x < width {
+            let (_, _, bg, _) = cell(x, y);
+            let start = x;
+            while x < width && cell(x, y).2 == bg {
+                x += 1;
+            }
+            if let Some(bg) = bg {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    start as f32 * cell_w,
+                    y as f32 * cell_h,
+                    (x - start) as f32 * cell_w,
+                    cell_h,
+                    hex(bg)
+                );
+            }
+        }
+
+        // text
+        let mut x = 0;
+        while x < width {
+            let (_, fg, _, attrs) = cell(x, y);
+            let start = x;
+            let mut text = String::new();
+            while x < width && cell(x, y).1 == fg && cell(x, y).3 == attrs {
+                let chr = cell(x, y).0;
+                chr.push_to(&mut text);
+                // the continuation cell of a wide char is covered by the char itself
+                x = (x + chr.width()).min(width);
+            }
+            if text.trim().is_empty() {
+                continue;
+            }
+            let mut style = String::new();
+            if attrs.contains(CellAttributes::BOLD) {
+                style.push_str(" font-weight=\"bold\"");
+            }
+            if attrs.contains(CellAttributes::DIM) {
+                style.push_str(" fill-opacity=\"0.6\"");
+            }
+            if attrs.contains(CellAttributes::ITALIC) {
+                style.push_str(" font-style=\"italic\"");
+            }
+            if let Some(decoration) = text_decoration(attrs) {
+                let _ = write!(style, " text-decoration=\"{}\"", decoration);
+            }
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" fill=\"{}\"{} textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" \
+                 xml:space=\"preserve\" dominant-baseline=\"text-before-edge\">{}</text>",
+                start as f32 * cell_w,
+                y as f32 * cell_h,
+                hex(fg.unwrap_or(options.default_fg)),
+                style,
+                (x - start) as f32 * cell_w,
+                escape_markup(&text)
+            );
+        }
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Handle to a slot inside of a GenerationalIdAllocator.
+///
+/// The index of a freed slot gets reused, but its generation is bumped,
+/// so an old handle never matches the slots new occupant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GenerationalId {
+    pub index: usize,
+    pub generation: u32,
+}
+
+impl fmt::Display for GenerationalId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Slot {
+    generation: u32,
+    occupied: bool,
+}
+
+/// slot-map style id generator, that never hands out the same id twice
+#[derive(Debug, Clone, Default)]
+pub struct GenerationalIdAllocator {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+impl GenerationalIdAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hand out a new id, reusing the index of a freed slot if possible
+    pub fn allocate(&mut self) -> GenerationalId {
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index];
+            slot.occupied = true;
+            GenerationalId {
+                index,
+                generation: slot.generation,
+            }
+        } else {
+            self.slots.push(Slot {
+                generation: 0,
+                occupied: true,
+            });
+            GenerationalId {
+                index: self.slots.len() - 1,
+                generation: 0,
+            }
+        }
+    }
+
+    /// Free the slot of the id, so every existing copy of it becomes stale.
+    ///
+    /// returns false, if the id was not alive
+    pub fn free(&mut self, id: GenerationalId) -> bool {
+        if !self.is_alive(id) {
+            return false;
+        }
+        let slot = &mut self.slots[id.index];
+        slot.occupied = false;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        true
+    }
+
+    /// returns true, if the id has been handed out and not been freed yet
+    pub fn is_alive(&self, id: GenerationalId) -> bool {
+        self.slots
+            .get(id.index)
+            .is_some_and(|slot| slot.occupied && slot.generation == id.generation)
+    }
+
+    /// returns true, if the id has been handed out, but has been freed since
+    pub fn is_stale(&self, id: GenerationalId) -> bool {
+        self.slots
+            .get(id.index)
+            .is_some_and(|slot| id.generation < slot.generation)
+    }
+}
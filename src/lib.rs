@@ -1,11 +1,17 @@
 pub mod rendering;
 pub use rendering::renderer::{ObjectId, Renderer, SpriteId};
 
+pub mod clock;
+pub use clock::{Clock, ManualClock, ScaledClock, SharedClock, SystemClock};
+
+pub mod generational_id;
+pub use generational_id::{GenerationalId, GenerationalIdAllocator};
+
 pub mod update_interval_handler;
 pub use update_interval_handler::{UpdateInterval, UpdateIntervalHandler};
 
 pub mod terminal_buffer;
-pub use terminal_buffer::{ScreenBuffer, SpriteDrawable};
+pub use terminal_buffer::{CellAttributes, CursorShape, ScreenBuffer, SpriteDrawable};
 
 pub mod drawable_traits;
 pub use drawable_traits::{
@@ -16,10 +22,13 @@ pub mod sprite_register;
 pub use sprite_register::{SpriteEntry, SpriteRegistry};
 
 pub mod term_utils;
-pub use term_utils::{initial_terminal_state, restore_terminal};
+pub use term_utils::{
+    InlineRegion, TerminalConfig, TerminalGuard, initial_inline_state, initial_terminal_state,
+    restore_inline_terminal, restore_terminal,
+};
 
 pub mod error;
-pub use error::{DrawError, FileError};
+pub use error::{DrawError, FileError, SnapshotError};
 
 pub mod display_screen;
 pub use display_screen::{Screen, ScreenKey};
@@ -33,27 +42,7 @@ pub use draw_object_builder::DrawObjectBuilder;
 pub mod input_handler;
 pub use input_handler::CrosstermEventManager;
 
-// for testing purposes only
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
-
-use crate::{
-    error::AppError,
-    terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation,
-};
-
-static TEST_DATA: Lazy<Mutex<Option<TerminalContentInformation>>> = Lazy::new(|| Mutex::new(None));
-
-pub fn set_test_data(data: TerminalContentInformation) {
-    let mut guard = TEST_DATA.lock().unwrap();
-    *guard = Some(data);
-}
-
-pub fn get_test_data() -> Option<TerminalContentInformation> {
-    let guard = TEST_DATA.lock().unwrap();
-    guard.clone()
-}
-
+use crate::error::AppError;
 use env_logger::Builder;
 use std::fs::File;
 use std::io::Write;
@@ -68,3 +57,8 @@ pub fn init_logger(path: &str) -> Result<(), AppError> {
         .init();
     Ok(())
 }
+
+pub mod testing;
+
+pub mod export;
+pub use export::{ExportOptions, export_html, export_svg};
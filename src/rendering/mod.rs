@@ -1,3 +1,6 @@
+pub mod panic_isolation;
+pub mod recorder;
+pub mod render_batch;
 pub mod render_handle;
 pub mod render_thread;
 pub mod renderer;
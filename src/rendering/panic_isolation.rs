@@ -0,0 +1,36 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+};
+
+thread_local! {
+    /// set, while code runs inside of catch_panic
+    static ISOLATING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether a panic on the current thread gets caught by catch_panic.
+/// Panic hooks use it to leave the terminal alone for panics, that the renderer survives
+pub fn panic_is_isolated() -> bool {
+    ISOLATING.get()
+}
+
+/// The message of a panic payload, empty if it is neither a &str nor a String
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+/// Run code of a drawable, turning a panic into its message.
+///
+/// Whatever the closure touched may be left half updated,
+/// so the object, that panicked, should not be used again
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let outer = ISOLATING.replace(true);
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATING.set(outer);
+    res.map_err(|payload| panic_message(&*payload))
+}
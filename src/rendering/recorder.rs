@@ -0,0 +1,87 @@
+use ascii_assets::{AsciiSprite, AsciiVideo, TerminalChar};
+use common_stdx::Rect;
+
+use crate::{DrawError, ScreenKey, terminal_buffer::StyledChar};
+
+/// The part of the terminal, that gets recorded
+#[derive(Debug, Clone, Copy)]
+pub enum RecordingArea {
+    FullScreen,
+    /// the area of the screen, at the time of each frame
+    Screen(ScreenKey),
+    /// p2 is exclusive, like the area of a screen
+    Rect(Rect<i32>),
+}
+
+/// Collects the composed content of every flush into the frames of an AsciiVideo.
+///
+/// The size of the video is taken from the first frame,
+/// later frames with a different size are cropped or padded with blank cells.
+#[derive(Debug)]
+pub struct FrameRecorder {
+    area: RecordingArea,
+    size: Option<(u16, u16)>,
+    frames: Vec<AsciiSprite>,
+}
+
+impl FrameRecorder {
+    pub fn new(area: RecordingArea) -> Self {
+        FrameRecorder {
+            area,
+            size: None,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn area(&self) -> RecordingArea {
+        self.area
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Record the part of the content inside of the rect.
+    /// `content` holds the whole terminal, unknown cells are recorded as blanks
+    pub fn record(
+        &mut self,
+        content: &[Option<StyledChar>],
+        terminal_size: (u16, u16),
+        rect: Rect<i32>,
+    ) -> Result<(), DrawError> {
+        let (width, height) = *self.size.get_or_insert((
+            (rect.p2.x - rect.p1.x).clamp(0, u16::MAX as i32) as u16,
+            (rect.p2.y - rect.p1.y).clamp(0, u16::MAX as i32) as u16,
+        ));
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for y in rect.p1.y..rect.p1.y + height as i32 {
+            for x in rect.p1.x..rect.p1.x + width as i32 {
+                let inside = x >= 0
+                    && y >= 0
+                    && x < rect.p2.x.min(terminal_size.0 as i32)
+                    && y < rect.p2.y.min(terminal_size.1 as i32);
+                let chr = if inside {
+                    content
+                        .get(y as usize * terminal_size.0 as usize + x as usize)
+                        .copied()
+                        .flatten()
+                        .map(|c| c.chr)
+                } else {
+                    None
+                };
+                pixels.push(chr.unwrap_or(TerminalChar::from_char(' ')));
+            }
+        }
+        self.frames.push(AsciiSprite::new(width, height, pixels)?);
+        Ok(())
+    }
+
+    /// Bake all recorded frames into a video
+    pub fn into_video(self) -> Result<AsciiVideo, DrawError> {
+        let Some((width, height)) = self.size else {
+            return Err(DrawError::EmptyRecording);
+        };
+        Ok(AsciiVideo::new(width, height, self.frames)?)
+    }
+}
@@ -0,0 +1,159 @@
+use common_stdx::Point;
+
+use crate::drawable_traits::{DrawableUpdate, typed_update};
+use crate::{DrawObject, DrawObjectKey, Drawable, ScreenKey};
+
+/// A single mutation queued inside of a RenderBatch
+pub enum BatchOperation {
+    RegisterDrawable {
+        screen_id: ScreenKey,
+        obj: DrawObject,
+    },
+    RemoveDrawable {
+        id: DrawObjectKey,
+    },
+    ExplicitRemoveDrawable {
+        id: DrawObjectKey,
+    },
+    DestroyDrawable {
+        id: DrawObjectKey,
+    },
+    ReplaceDrawable {
+        id: DrawObjectKey,
+        drawable: Box<dyn Drawable + Send + 'static>,
+    },
+    RenderDrawable {
+        key: DrawObjectKey,
+    },
+    UpdateDrawable {
+        key: DrawObjectKey,
+        update: DrawableUpdate,
+    },
+    MoveDrawableTo {
+        handle: DrawObjectKey,
+        new_pos: Point<i32>,
+    },
+    MoveDrawableBy {
+        handle: DrawObjectKey,
+        dx: i32,
+        dy: i32,
+    },
+    MoveDrawablePoint {
+        handle: DrawObjectKey,
+        point_index: usize,
+        new_pos: Point<i32>,
+    },
+    ReplaceDrawablePoints {
+        handle: DrawObjectKey,
+        new_points: Vec<Point<i32>>,
+    },
+}
+
+/// The result of a single successful BatchOperation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutcome {
+    /// the object has been registered under this key
+    Registered(DrawObjectKey),
+    Applied,
+}
+
+/// Collects mutations, that get applied on the render thread in one go.
+///
+/// No other command is handled while the batch is applied,
+/// and the terminal only gets updated once at the end of it.
+#[derive(Default)]
+pub struct RenderBatch {
+    operations: Vec<BatchOperation>,
+}
+
+impl RenderBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn into_operations(self) -> Vec<BatchOperation> {
+        self.operations
+    }
+
+    pub fn push(&mut self, operation: BatchOperation) -> &mut Self {
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn register_drawable(&mut self, screen_id: ScreenKey, obj: DrawObject) -> &mut Self {
+        self.push(BatchOperation::RegisterDrawable { screen_id, obj })
+    }
+
+    pub fn remove_drawable(&mut self, id: DrawObjectKey) -> &mut Self {
+        self.push(BatchOperation::RemoveDrawable { id })
+    }
+
+    pub fn explicit_remove_drawable(&mut self, id: DrawObjectKey) -> &mut Self {
+        self.push(BatchOperation::ExplicitRemoveDrawable { id })
+    }
+
+    pub fn destroy_drawable(&mut self, id: DrawObjectKey) -> &mut Self {
+        self.push(BatchOperation::DestroyDrawable { id })
+    }
+
+    pub fn replace_drawable(
+        &mut self,
+        id: DrawObjectKey,
+        drawable: Box<dyn Drawable + Send + 'static>,
+    ) -> &mut Self {
+        self.push(BatchOperation::ReplaceDrawable { id, drawable })
+    }
+
+    pub fn render_drawable(&mut self, key: DrawObjectKey) -> &mut Self {
+        self.push(BatchOperation::RenderDrawable { key })
+    }
+
+    /// mutate the drawable as its concrete type, see RenderHandle::update
+    pub fn update<T, F>(&mut self, key: DrawObjectKey, update: F) -> &mut Self
+    where
+        T: Drawable,
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        self.push(BatchOperation::UpdateDrawable {
+            key,
+            update: typed_update(update),
+        })
+    }
+
+    pub fn move_drawable_to(&mut self, handle: DrawObjectKey, new_pos: Point<i32>) -> &mut Self {
+        self.push(BatchOperation::MoveDrawableTo { handle, new_pos })
+    }
+
+    pub fn move_drawable_by(&mut self, handle: DrawObjectKey, dx: i32, dy: i32) -> &mut Self {
+        self.push(BatchOperation::MoveDrawableBy { handle, dx, dy })
+    }
+
+    pub fn move_drawable_point(
+        &mut self,
+        handle: DrawObjectKey,
+        point_index: usize,
+        new_pos: Point<i32>,
+    ) -> &mut Self {
+        self.push(BatchOperation::MoveDrawablePoint {
+            handle,
+            point_index,
+            new_pos,
+        })
+    }
+
+    pub fn replace_drawable_points(
+        &mut self,
+        handle: DrawObjectKey,
+        new_points: Vec<Point<i32>>,
+    ) -> &mut Self {
+        self.push(BatchOperation::ReplaceDrawablePoints { handle, new_points })
+    }
+}
@@ -1,12 +1,20 @@
-use std::sync::mpsc;
+use std::sync::{Arc, Mutex, PoisonError, Weak, mpsc};
+use std::thread::{self, JoinHandle};
 
+use ascii_assets::AsciiVideo;
 use common_stdx::Point;
 
 use crate::Drawable;
 use crate::display_screen::AreaRect;
-use crate::error::AppError;
+use crate::drawable_traits::{DrawableUpdate, typed_update};
+use crate::error::{AppError, ErrorPolicy, RenderError};
+use crate::rendering::panic_isolation::panic_message;
+use crate::rendering::recorder::RecordingArea;
+use crate::rendering::render_batch::{BatchOutcome, RenderBatch};
 use crate::rendering::renderer::{Buffered, Instant, RenderModeBehavior};
-use crate::{DrawObject, DrawObjectKey, ScreenKey, SpriteId};
+use crate::terminal_buffer::CursorShape;
+use crate::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation;
+use crate::{DrawObject, DrawObjectKey, ScreenKey, SharedClock, SpriteId, TerminalGuard};
 
 pub enum RenderCommand {
     CreateScreen {
@@ -14,6 +22,10 @@ pub enum RenderCommand {
         layer: usize,
         resp: mpsc::Sender<Result<ScreenKey, AppError>>,
     },
+    DestroyScreen {
+        screen_id: ScreenKey,
+        resp: mpsc::Sender<Result<(), AppError>>,
+    },
     ChangeScreenArea {
         screen_id: ScreenKey,
         new_area: AreaRect,
@@ -26,6 +38,7 @@ pub enum RenderCommand {
     AddOnScreenSelect {
         screen_id: ScreenKey,
         callback: Box<dyn FnMut() + 'static + Send>,
+        resp: mpsc::Sender<Result<(), AppError>>,
     },
     ChangeScreenLayer {
         screen_id: ScreenKey,
@@ -45,6 +58,10 @@ pub enum RenderCommand {
         id: DrawObjectKey,
         resp: mpsc::Sender<Result<(), AppError>>,
     },
+    DestroyDrawable {
+        id: DrawObjectKey,
+        resp: mpsc::Sender<Result<(), AppError>>,
+    },
     ReplaceDrawable {
         id: DrawObjectKey,
         drawable: Box<dyn Drawable + Send + 'static>,
@@ -54,10 +71,26 @@ pub enum RenderCommand {
         path: String,
         resp: mpsc::Sender<Result<SpriteId, AppError>>,
     },
+    RegisterSprite {
+        video: AsciiVideo,
+        resp: mpsc::Sender<Result<SpriteId, AppError>>,
+    },
+    StartRecording {
+        area: RecordingArea,
+        resp: mpsc::Sender<Result<(), AppError>>,
+    },
+    StopRecording {
+        resp: mpsc::Sender<Result<AsciiVideo, AppError>>,
+    },
     RenderDrawable {
         key: DrawObjectKey,
         resp: mpsc::Sender<Result<(), AppError>>,
     },
+    UpdateDrawable {
+        key: DrawObjectKey,
+        update: DrawableUpdate,
+        resp: mpsc::Sender<Result<(), AppError>>,
+    },
     RenderScreen {
         screen_id: ScreenKey,
         resp: mpsc::Sender<Result<(), AppError>>,
@@ -66,6 +99,15 @@ pub enum RenderCommand {
         new_size: (u16, u16),
         resp: mpsc::Sender<Result<(), AppError>>,
     },
+    Snapshot {
+        resp: mpsc::Sender<Result<Option<TerminalContentInformation>, AppError>>,
+    },
+    FrameHistory {
+        resp: mpsc::Sender<Result<Vec<TerminalContentInformation>, AppError>>,
+    },
+    ComposedContent {
+        resp: mpsc::Sender<Result<TerminalContentInformation, AppError>>,
+    },
     GetTerminalSize {
         resp: mpsc::Sender<Result<(u16, u16), AppError>>,
     },
@@ -98,9 +140,37 @@ pub enum RenderCommand {
         new_points: Vec<Point<i32>>,
         resp: mpsc::Sender<Result<(), AppError>>,
     },
+    Batch {
+        batch: RenderBatch,
+        resp: mpsc::Sender<Result<Vec<Result<BatchOutcome, AppError>>, AppError>>,
+    },
+    SetClock {
+        clock: SharedClock,
+    },
     SetUpdateInterval {
         amount: usize,
     },
+    SetCursorVisible {
+        visible: bool,
+    },
+    SetCursorPosition {
+        screen_id: ScreenKey,
+        pos: Point<i32>,
+        resp: mpsc::Sender<Result<(), AppError>>,
+    },
+    SetCursorShape {
+        shape: CursorShape,
+        blinking: bool,
+    },
+    PrintAbove {
+        text: String,
+    },
+    SubscribeErrors {
+        tx: mpsc::Sender<RenderError>,
+    },
+    SetErrorPolicy {
+        policy: ErrorPolicy,
+    },
     IntoInstant,
 
     IntoBuffered,
@@ -108,10 +178,64 @@ pub enum RenderCommand {
     Shutdown,
 }
 
+/// Shared by all clones of a RenderHandle.
+/// Shuts the render thread down, once the last handle is dropped
+#[derive(Debug)]
+pub(crate) struct Lifecycle {
+    tx: mpsc::Sender<RenderCommand>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+    /// restored after the render thread has ended
+    terminal: Mutex<Option<TerminalGuard>>,
+}
+
+impl Lifecycle {
+    pub(crate) fn new(tx: mpsc::Sender<RenderCommand>, thread: JoinHandle<()>) -> Self {
+        Lifecycle {
+            tx,
+            thread: Mutex::new(Some(thread)),
+            terminal: Mutex::new(None),
+        }
+    }
+
+    fn shutdown(&self) -> Result<(), AppError> {
+        // ignore error: means the render thread has already ended
+        let _ = self.tx.send(RenderCommand::Shutdown);
+        let handle = self
+            .thread
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let result = match handle {
+            // a handle dropped on the render thread itself cannot wait for it
+            Some(handle) if handle.thread().id() != thread::current().id() => handle
+                .join()
+                .map_err(|payload| AppError::RenderThreadPanicked(panic_message(&*payload))),
+            _ => Ok(()),
+        };
+        self.terminal
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        result
+    }
+}
+
+impl Drop for Lifecycle {
+    fn drop(&mut self) {
+        if let Err(e) = self.shutdown() {
+            log::error!("Failed to shut down the renderer: {}", e);
+        }
+    }
+}
+
+/// Shutting down on the last drop only works, if no handle is kept by the render thread itself.
+/// Callbacks and drawables should get a WeakRenderHandle from downgrade,
+/// or the renderer has to be stopped with shutdown
 #[derive(Debug, Clone)]
 pub struct RenderHandle<M> {
     pub tx: mpsc::Sender<RenderCommand>,
     pub _mode: std::marker::PhantomData<M>,
+    pub(crate) lifecycle: Arc<Lifecycle>,
 }
 
 impl<M> RenderHandle<M>
@@ -137,6 +261,11 @@ where
         self.send_and_wait(|resp| RenderCommand::CreateScreen { rect, layer, resp })
     }
 
+    /// destroy the screen and all of its objects, its key becomes stale
+    pub fn destroy_screen(&self, screen_id: ScreenKey) -> Result<(), AppError> {
+        self.send_and_wait(|resp| RenderCommand::DestroyScreen { screen_id, resp })
+    }
+
     pub fn change_screen_area(
         &self,
         screen_id: ScreenKey,
@@ -157,9 +286,10 @@ where
     where
         F: FnMut() + 'static + Send,
     {
-        self.send_and_wait(|_| RenderCommand::AddOnScreenSelect {
+        self.send_and_wait(|resp| RenderCommand::AddOnScreenSelect {
             screen_id,
             callback: Box::new(callback),
+            resp,
         })
     }
 
@@ -204,10 +334,40 @@ where
         self.send_and_wait(|resp| RenderCommand::RegisterSpriteFromSource { path, resp })
     }
 
+    /// Register a video, that is already in memory, as a sprite
+    pub fn register_sprite(&self, video: AsciiVideo) -> Result<SpriteId, AppError> {
+        self.send_and_wait(|resp| RenderCommand::RegisterSprite { video, resp })
+    }
+
+    /// Record every flushed frame inside of the area, until stop_recording is called.
+    /// Starting a new recording drops the current one
+    pub fn start_recording(&self, area: RecordingArea) -> Result<(), AppError> {
+        self.send_and_wait(|resp| RenderCommand::StartRecording { area, resp })
+    }
+
+    /// Stop recording and return the recorded frames as a video,
+    /// which can be saved with AsciiVideo::write_to_file or registered as a sprite
+    pub fn stop_recording(&self) -> Result<AsciiVideo, AppError> {
+        self.send_and_wait(|resp| RenderCommand::StopRecording { resp })
+    }
+
     pub fn render_drawable(&self, key: DrawObjectKey) -> Result<(), AppError> {
         self.send_and_wait(|resp| RenderCommand::RenderDrawable { key, resp })
     }
 
+    /// Mutate the drawable behind the key as its concrete type T.
+    /// Only the updated object gets re-rendered.
+    ///
+    /// Fails with DrawError::WrongDrawableType, if the drawable is not a T
+    pub fn update<T, F>(&self, key: DrawObjectKey, update: F) -> Result<(), AppError>
+    where
+        T: Drawable,
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        let update = typed_update(update);
+        self.send_and_wait(|resp| RenderCommand::UpdateDrawable { key, update, resp })
+    }
+
     pub fn render_screen(&self, screen_id: ScreenKey) -> Result<(), AppError> {
         self.send_and_wait(|resp| RenderCommand::RenderScreen { screen_id, resp })
     }
@@ -216,6 +376,23 @@ where
         self.send_and_wait(|resp| RenderCommand::HandleResize { new_size, resp })
     }
 
+    /// The content of the renderers last flush, only available for headless drawers like the TestCellDrawer.
+    /// All commands sent before are fully drawn, when this returns
+    pub fn snapshot(&self) -> Result<Option<TerminalContentInformation>, AppError> {
+        self.send_and_wait(|resp| RenderCommand::Snapshot { resp })
+    }
+
+    /// The last flushed frames of a headless drawer, the oldest first
+    pub fn frame_history(&self) -> Result<Vec<TerminalContentInformation>, AppError> {
+        self.send_and_wait(|resp| RenderCommand::FrameHistory { resp })
+    }
+
+    /// The composed content of all screens, independent of the drawer.
+    /// Can be passed to the exporters in crate::export
+    pub fn composed_content(&self) -> Result<TerminalContentInformation, AppError> {
+        self.send_and_wait(|resp| RenderCommand::ComposedContent { resp })
+    }
+
     pub fn get_terminal_size(&self) -> Result<(u16, u16), AppError> {
         self.send_and_wait(|resp| RenderCommand::GetTerminalSize { resp })
     }
@@ -264,6 +441,45 @@ where
         self.send_and_wait(|resp| RenderCommand::GetAmountOfPoints { handle, resp })
     }
 
+    /// replace the clock, that lifetimes and animations read their time from
+    pub fn set_clock(&self, clock: SharedClock) -> Result<(), AppError> {
+        self.send(RenderCommand::SetClock { clock })
+    }
+
+    /// Show the hardware cursor, once it has been placed with set_cursor_position
+    pub fn show_cursor(&self) -> Result<(), AppError> {
+        self.send(RenderCommand::SetCursorVisible { visible: true })
+    }
+
+    pub fn hide_cursor(&self) -> Result<(), AppError> {
+        self.send(RenderCommand::SetCursorVisible { visible: false })
+    }
+
+    /// Place the cursor relative to the top left corner of the screen.
+    /// It follows the screen when it moves and is hidden while outside of it
+    pub fn set_cursor_position(
+        &self,
+        screen_id: ScreenKey,
+        pos: impl Into<Point<i32>>,
+    ) -> Result<(), AppError> {
+        let pos = pos.into();
+        self.send_and_wait(|resp| RenderCommand::SetCursorPosition {
+            screen_id,
+            pos,
+            resp,
+        })
+    }
+
+    pub fn set_cursor_shape(&self, shape: CursorShape, blinking: bool) -> Result<(), AppError> {
+        self.send(RenderCommand::SetCursorShape { shape, blinking })
+    }
+
+    /// Print lines above the inline region of the renderer, which scroll into the scrollback.
+    /// Use this instead of println!, while rendering inline
+    pub fn print_above(&self, text: impl Into<String>) -> Result<(), AppError> {
+        self.send(RenderCommand::PrintAbove { text: text.into() })
+    }
+
     pub fn set_update_interval(&self, amount: usize) -> Result<(), AppError> {
         self.send(RenderCommand::SetUpdateInterval { amount })?;
         Ok(())
@@ -282,14 +498,87 @@ where
         Ok(())
     }
 
+    /// hide the drawable, render_drawable shows it again
     pub fn explicit_remove_drawable(&self, id: &DrawObjectKey) -> Result<(), AppError> {
         self.send_and_wait(|resp| RenderCommand::ExplicitRemoveDrawable { id: *id, resp })
     }
 
-    /// ask thread to shutdown (no response)
-    pub fn shutdown(&self) {
-        // ignore error: means receiver already dropped
-        let _ = self.tx.send(RenderCommand::Shutdown);
+    /// Queue up multiple mutations and apply them in a single round-trip.
+    /// The terminal only gets refreshed once, after all operations have been applied.
+    /// returns the result of every queued operation, in order
+    pub fn batch<F>(&self, build: F) -> Result<Vec<Result<BatchOutcome, AppError>>, AppError>
+    where
+        F: FnOnce(&mut RenderBatch),
+    {
+        let mut batch = RenderBatch::new();
+        build(&mut batch);
+        self.send_and_wait(|resp| RenderCommand::Batch { batch, resp })
+    }
+
+    /// remove the drawable for good, every copy of its key becomes stale
+    pub fn destroy_drawable(&self, id: DrawObjectKey) -> Result<(), AppError> {
+        self.send_and_wait(|resp| RenderCommand::DestroyDrawable { id, resp })
+    }
+
+    /// Stop the render thread and wait for it, after the drawer has flushed.
+    /// Restores the terminal, if a guard has been handed over with restore_terminal_on_shutdown.
+    /// Happens automatically, when the last handle is dropped,
+    /// unless a callback or drawable on the render thread still owns one.
+    /// Every handle fails with a SendError afterwards
+    pub fn shutdown(&self) -> Result<(), AppError> {
+        self.lifecycle.shutdown()
+    }
+
+    /// Receive the errors of the render thread, that no caller has been waiting for,
+    /// like failing input handlers or drawer flushes.
+    /// Only the latest receiver gets them, without one they are logged
+    pub fn errors(&self) -> Result<mpsc::Receiver<RenderError>, AppError> {
+        let (tx, rx) = mpsc::channel();
+        self.send(RenderCommand::SubscribeErrors { tx })?;
+        Ok(rx)
+    }
+
+    /// decide, whether the render thread stops after an error
+    pub fn set_error_policy(&self, policy: ErrorPolicy) -> Result<(), AppError> {
+        self.send(RenderCommand::SetErrorPolicy { policy })
+    }
+
+    /// a handle for callbacks and drawables, that does not keep the renderer running
+    pub fn downgrade(&self) -> WeakRenderHandle<M> {
+        WeakRenderHandle {
+            tx: self.tx.clone(),
+            _mode: std::marker::PhantomData,
+            lifecycle: Arc::downgrade(&self.lifecycle),
+        }
+    }
+
+    /// Keep the guard until the render thread has ended, so the terminal gets restored
+    /// only after the last frame has been drawn
+    pub fn restore_terminal_on_shutdown(&self, guard: TerminalGuard) {
+        *self
+            .lifecycle
+            .terminal
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(guard);
+    }
+}
+
+/// Handle, that does not count towards shutting down on the last drop
+#[derive(Debug, Clone)]
+pub struct WeakRenderHandle<M> {
+    tx: mpsc::Sender<RenderCommand>,
+    _mode: std::marker::PhantomData<M>,
+    lifecycle: Weak<Lifecycle>,
+}
+
+impl<M> WeakRenderHandle<M> {
+    /// the full handle, as long as any other one is still alive
+    pub fn upgrade(&self) -> Option<RenderHandle<M>> {
+        self.lifecycle.upgrade().map(|lifecycle| RenderHandle {
+            tx: self.tx.clone(),
+            _mode: std::marker::PhantomData,
+            lifecycle,
+        })
     }
 }
 
@@ -303,6 +592,7 @@ impl RenderHandle<Buffered> {
         Ok(RenderHandle {
             tx: self.tx.clone(),
             _mode: std::marker::PhantomData,
+            lifecycle: self.lifecycle.clone(),
         })
     }
 }
@@ -313,6 +603,7 @@ impl RenderHandle<Instant> {
         Ok(RenderHandle {
             tx: self.tx.clone(),
             _mode: std::marker::PhantomData,
+            lifecycle: self.lifecycle.clone(),
         })
     }
 }
@@ -2,6 +2,7 @@
 use crate::CrosstermEventManager;
 use crate::{
     Renderer, ScreenBuffer,
+    error::{AppError, ErrorPolicy, RenderError, RenderPhase},
     input_handler::{
         hook::EventHook,
         manager::{
@@ -9,12 +10,45 @@ use crate::{
         },
     },
     rendering::{
-        render_handle::{RenderCommand, RenderHandle},
+        render_handle::{Lifecycle, RenderCommand, RenderHandle},
         renderer::RenderModeBehavior,
     },
     terminal_buffer::CellDrawer,
 };
-use std::{sync::mpsc, thread, time::Duration};
+use std::{
+    sync::{Arc, mpsc},
+    thread,
+    time::Duration,
+};
+
+/// Hands errors, that no caller waits for, to the error subscriber
+#[derive(Default)]
+struct ErrorReporter {
+    subscriber: Option<mpsc::Sender<RenderError>>,
+    policy: ErrorPolicy,
+}
+
+impl ErrorReporter {
+    /// report the error of the result, returns whether the render thread should stop
+    fn report<E: Into<AppError>>(&mut self, phase: RenderPhase, res: Result<(), E>) -> bool {
+        let Err(error) = res else {
+            return false;
+        };
+        let error = RenderError {
+            phase,
+            error: error.into(),
+        };
+        let unsent = match &self.subscriber {
+            Some(tx) => tx.send(error).err().map(|e| e.0),
+            None => Some(error),
+        };
+        if let Some(error) = unsent {
+            self.subscriber = None;
+            log::error!("{}", error);
+        }
+        self.policy == ErrorPolicy::Stop
+    }
+}
 
 fn start_thread<B, M>(
     mut renderer: Renderer<B, M>,
@@ -44,22 +78,58 @@ where
             .unwrap();
     };
     // spawn the renderer loop
-    thread::spawn(move || {
-        loop {
-            let _ = renderer.remove_all_framebased_objects();
+    let thread = thread::spawn(move || {
+        let mut errors = ErrorReporter::default();
+        'render: loop {
+            let res = renderer.remove_all_framebased_objects();
+            if errors.report(RenderPhase::FramebasedCleanup, res) {
+                break 'render;
+            }
+            let res = renderer.check_if_object_lifetime_ended();
+            if errors.report(RenderPhase::LifetimeCheck, res) {
+                break 'render;
+            }
             // dump all key messages
             if let Some(hook) = &mut key_hook {
-                let _ = renderer.handle_screen_selection(hook);
+                let res = renderer.handle_screen_selection(hook);
+                if errors.report(RenderPhase::ScreenSelection, res) {
+                    break 'render;
+                }
                 let msgs = hook.dump_accumulation();
                 for msg in msgs {
-                    let _ = renderer.handle_input_message(msg);
+                    let res = renderer.handle_input_message(msg);
+                    if errors.report(RenderPhase::Input, res) {
+                        break 'render;
+                    }
                 }
             }
             // dump all mouse messages
             if let Some(hook) = &mut mouse_hook {
                 let msgs = hook.dump_accumulation();
                 for msg in msgs {
-                    let _ = renderer.handle_input_message(msg);
+                    let res = renderer.handle_input_message(msg);
+                    if errors.report(RenderPhase::Input, res) {
+                        break 'render;
+                    }
+                }
+            }
+            let panics = match renderer.take_quarantined() {
+                Ok(panics) => panics,
+                Err(e) => {
+                    if errors.report(RenderPhase::Quarantine, Err(e)) {
+                        break 'render;
+                    }
+                    Vec::new()
+                }
+            };
+            for e in panics {
+                if errors.report(RenderPhase::Quarantine, Err(e)) {
+                    break 'render;
+                }
+            }
+            for e in renderer.take_drawer_errors() {
+                if errors.report(RenderPhase::Drawer, Err(e)) {
+                    break 'render;
                 }
             }
             while let Ok(cmd) = rx.recv_timeout(Duration::from_millis(2)) {
@@ -69,6 +139,10 @@ where
                         let key = renderer.create_screen(rect, layer);
                         let _ = resp.send(Ok(key));
                     }
+                    RenderCommand::DestroyScreen { screen_id, resp } => {
+                        let res = renderer.destroy_screen(screen_id).map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
                     RenderCommand::ChangeScreenArea {
                         screen_id,
                         new_area,
@@ -88,8 +162,12 @@ where
                     RenderCommand::AddOnScreenSelect {
                         screen_id,
                         callback,
+                        resp,
                     } => {
-                        renderer.add_on_screen_select(screen_id, callback);
+                        let res = renderer
+                            .add_on_screen_select(screen_id, callback)
+                            .map_err(Into::into);
+                        let _ = resp.send(res);
                     }
                     RenderCommand::ChangeScreenLayer {
                         screen_id,
@@ -119,6 +197,10 @@ where
                         let res = renderer.explicit_remove_drawable(&id).map_err(Into::into);
                         let _ = resp.send(res);
                     }
+                    RenderCommand::DestroyDrawable { id, resp } => {
+                        let res = renderer.destroy_drawable(id).map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
                     RenderCommand::ReplaceDrawable { id, drawable, resp } => {
                         let res = renderer.replace_drawable(id, drawable).map_err(Into::into);
                         let _ = resp.send(res);
@@ -127,10 +209,25 @@ where
                         let res = renderer.register_sprite_from_source(&path);
                         let _ = resp.send(res);
                     }
+                    RenderCommand::RegisterSprite { video, resp } => {
+                        let _ = resp.send(Ok(renderer.register_sprite(video)));
+                    }
+                    RenderCommand::StartRecording { area, resp } => {
+                        let res = renderer.start_recording(area).map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
+                    RenderCommand::StopRecording { resp } => {
+                        let res = renderer.stop_recording().map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
                     RenderCommand::RenderDrawable { key, resp } => {
                         let res = renderer.render_drawable(key).map_err(Into::into);
                         let _ = resp.send(res);
                     }
+                    RenderCommand::UpdateDrawable { key, update, resp } => {
+                        let res = renderer.update_drawable(key, update).map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
                     RenderCommand::RenderScreen { screen_id, resp } => {
                         let res = renderer.render_screen(screen_id);
                         let _ = resp.send(res);
@@ -139,6 +236,17 @@ where
                         let res = renderer.handle_resize(new_size).map_err(Into::into);
                         let _ = resp.send(res);
                     }
+                    RenderCommand::Snapshot { resp } => {
+                        let res = renderer.snapshot().map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
+                    RenderCommand::FrameHistory { resp } => {
+                        let res = renderer.frame_history().map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
+                    RenderCommand::ComposedContent { resp } => {
+                        let _ = resp.send(Ok(renderer.composed_content()));
+                    }
                     RenderCommand::GetTerminalSize { resp } => {
                         let size = renderer.get_terminal_size();
                         let _ = resp.send(Ok(size));
@@ -193,24 +301,62 @@ where
                             .map_err(Into::into);
                         let _ = resp.send(res);
                     }
+                    RenderCommand::Batch { batch, resp } => {
+                        let res = renderer.apply_batch(batch).map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
+                    RenderCommand::SetClock { clock } => {
+                        renderer.set_clock(clock);
+                    }
                     RenderCommand::SetUpdateInterval { amount } => {
                         renderer.set_update_interval(amount);
                     }
+                    RenderCommand::SetCursorVisible { visible } => {
+                        if visible {
+                            renderer.show_cursor();
+                        } else {
+                            renderer.hide_cursor();
+                        }
+                    }
+                    RenderCommand::SetCursorPosition {
+                        screen_id,
+                        pos,
+                        resp,
+                    } => {
+                        let res = renderer
+                            .set_cursor_position(screen_id, pos)
+                            .map_err(Into::into);
+                        let _ = resp.send(res);
+                    }
+                    RenderCommand::SetCursorShape { shape, blinking } => {
+                        renderer.set_cursor_shape(shape, blinking);
+                    }
+                    RenderCommand::PrintAbove { text } => {
+                        renderer.print_above(text);
+                    }
+                    RenderCommand::SubscribeErrors { tx } => {
+                        errors.subscriber = Some(tx);
+                    }
+                    RenderCommand::SetErrorPolicy { policy } => {
+                        errors.policy = policy;
+                    }
                     RenderCommand::IntoInstant => {
                         renderer = renderer.into_instant();
                     }
                     RenderCommand::IntoBuffered => {
                         renderer = renderer.into_buffered();
                     }
-                    RenderCommand::Shutdown => break,
+                    RenderCommand::Shutdown => break 'render,
                 }
             }
         }
+        // dropping the renderer flushes and joins the drawer thread
     });
 
     RenderHandle {
-        tx,
+        tx: tx.clone(),
         _mode: std::marker::PhantomData,
+        lifecycle: Arc::new(Lifecycle::new(tx, thread)),
     }
 }
 
@@ -222,6 +368,23 @@ where
 {
     start_thread(renderer, None)
 }
+
+/// Start the renderer with the input of any event source, not only the crossterm event manager.
+/// The render thread subscribes to all key and mouse events through a hook of the handler
+#[cfg(feature = "test-support")]
+#[doc(hidden)]
+pub fn start_renderer_with_event_handler<B, M>(
+    renderer: Renderer<B, M>,
+    event_handler: &EventHandler,
+) -> RenderHandle<M>
+where
+    B: ScreenBuffer + Send + 'static,
+    B::Drawer: CellDrawer + Send + 'static,
+    M: RenderModeBehavior + Send + 'static,
+{
+    start_thread(renderer, Some(event_handler.create_hook()))
+}
+
 #[cfg(feature = "screen_select_subscription")]
 pub fn start_renderer_with_input<B, M>(
     mut renderer: Renderer<B, M>,
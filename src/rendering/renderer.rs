@@ -2,23 +2,28 @@ use crate::Drawable;
 use crate::ScreenBuffer;
 use crate::display_screen::AreaRect;
 use crate::drawable_register::ObjectLifetime;
+use crate::drawable_traits::DrawableUpdate;
 use crate::input_handler::hook::EventHook;
 use crate::input_handler::manager::{MouseMessage, SubscriptionMessage, TargetScreen};
 use crate::input_handler::screen_select_handler::ScreenSelectHMsg;
 use crate::input_handler::screen_select_handler::ScreenSelectHandler;
-use crate::terminal_buffer::CellDrawer;
+use crate::rendering::recorder::{FrameRecorder, RecordingArea};
+use crate::rendering::render_batch::{BatchOperation, BatchOutcome, RenderBatch};
+use crate::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation;
+use crate::terminal_buffer::{CellDrawer, CursorShape, CursorState};
 use crate::{
-    DrawError, DrawObject, DrawObjectKey, DrawObjectLibrary, Screen, ScreenKey, SpriteEntry,
-    SpriteRegistry, error::AppError,
+    DrawError, DrawObject, DrawObjectKey, DrawObjectLibrary, GenerationalId,
+    GenerationalIdAllocator, Screen, ScreenKey, SharedClock, SpriteEntry, SpriteRegistry,
+    error::AppError,
 };
 use ascii_assets::AsciiVideo;
 use common_stdx::{Point, Rect};
 use log::info;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::TryRecvError;
 
 pub type SpriteId = usize;
-pub type ObjectId = usize;
+pub type ObjectId = GenerationalId;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Instant;
@@ -57,6 +62,10 @@ impl RenderModeBehavior for Instant {
         renderer.render_drawable(object_key)
     }
     fn refresh<B: ScreenBuffer>(renderer: &mut Renderer<B, Self>) -> Result<(), DrawError> {
+        // batches refresh once, after all of their operations have been applied
+        if renderer.batch_dirty_screens.is_some() {
+            return Ok(());
+        }
         renderer.forced_refresh()?;
         Ok(())
     }
@@ -80,6 +89,10 @@ impl RenderModeBehavior for Instant {
     where
         Self: Sized,
     {
+        if let Some(dirty_screens) = &mut renderer.batch_dirty_screens {
+            dirty_screens.insert(screen_id);
+            return Ok(());
+        }
         info!("rendering full screen");
         if let Some(s) = renderer.screens.get_mut(&screen_id) {
             s.render_all(
@@ -134,12 +147,23 @@ where
     B::Drawer: CellDrawer,
 {
     screens: HashMap<ScreenKey, Screen>,
+    screen_ids: GenerationalIdAllocator,
     obj_library: DrawObjectLibrary,
     screen_buffer: B,
     sprites: SpriteRegistry,
     update_interval_expand_amount: usize,
     terminal_size: (u16, u16),
     screen_select_handler: Option<ScreenSelectHandler>,
+    /// screens, that need to be rendered once the current batch has been applied
+    batch_dirty_screens: Option<HashSet<ScreenKey>>,
+    clock: SharedClock,
+    recorder: Option<FrameRecorder>,
+    /// visibility and shape of the cursor, its position is resolved from the anchor
+    cursor: CursorState,
+    /// screen and position relative to it, that the cursor is placed at
+    cursor_anchor: Option<(ScreenKey, Point<i32>)>,
+    /// the cursor state, that the drawer has last been given
+    sent_cursor: Option<CursorState>,
     _mode: std::marker::PhantomData<M>,
 }
 
@@ -158,16 +182,47 @@ where
         self.terminal_size
     }
 
+    /// Replace the clock, that all time based behavior reads from,
+    /// and hand it to all existing drawables
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        let old_now = self.clock.now();
+        let now = clock.now();
+        for obj in self.obj_library.all_objects.values_mut() {
+            // lifetimes keep their remaining duration on the new clock
+            let lived = old_now.saturating_duration_since(obj.creation_time);
+            obj.creation_time = now.checked_sub(lived).unwrap_or(now);
+            obj.drawable.attach_clock(clock.clone());
+        }
+        self.clock = clock;
+    }
+
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Create a new renderer with an initial terminal size.
     pub fn create_renderer(size: (u16, u16)) -> Self {
+        Self::create_renderer_with_buffer(size, B::new(size))
+    }
+
+    /// Create a new renderer around an already existing screen buffer,
+    /// for example one created with DefaultScreenBuffer::with_drawer
+    pub fn create_renderer_with_buffer(size: (u16, u16), screen_buffer: B) -> Self {
         Renderer::<B, M> {
             obj_library: DrawObjectLibrary::new(),
             screens: HashMap::new(),
-            screen_buffer: B::new(size),
+            screen_ids: GenerationalIdAllocator::new(),
+            screen_buffer,
             sprites: SpriteRegistry::new(),
             update_interval_expand_amount: 50000,
             terminal_size: size,
             screen_select_handler: None,
+            batch_dirty_screens: None,
+            clock: SharedClock::default(),
+            recorder: None,
+            cursor: CursorState::default(),
+            cursor_anchor: None,
+            sent_cursor: None,
             _mode: std::marker::PhantomData,
         }
     }
@@ -180,17 +235,29 @@ where
         new_id
     }
 
+    /// Destroy a screen together with all of its objects.
+    /// The area it covered gets repainted and its key, as well as the keys of its objects, become stale
+    pub fn destroy_screen(&mut self, screen_id: ScreenKey) -> Result<(), DrawError> {
+        if let Some(s) = self.screens.get_mut(&screen_id) {
+            s.remove_all(&mut self.screen_buffer, &mut self.obj_library)?;
+        } else {
+            return Err(self.missing_screen_error(screen_id));
+        }
+        // dropping the screen also drops its on select callback
+        self.screens.remove(&screen_id);
+        self.obj_library.remove_screen_objs(screen_id);
+        self.screen_ids.free(screen_id);
+        M::refresh(self)?;
+        Ok(())
+    }
+
     pub fn change_screen_area(
         &mut self,
         screen_id: ScreenKey,
         new_area: AreaRect,
     ) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&screen_id) {
-            s.remove_all(
-                &mut self.screen_buffer,
-                &mut self.obj_library,
-                &self.sprites,
-            )?;
+            s.remove_all(&mut self.screen_buffer, &mut self.obj_library)?;
             s.change_screen_area(new_area);
             let ids = s.draw_objects.to_vec();
             let screen_rect = s.rect();
@@ -215,17 +282,13 @@ where
             M::refresh(self)?;
             Ok(())
         } else {
-            Err(DrawError::DisplayKeyNotFound(screen_id))
+            Err(self.missing_screen_error(screen_id))
         }
     }
 
     pub fn fit_screen_area_to_contents(&mut self, screen_id: ScreenKey) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&screen_id) {
-            s.remove_all(
-                &mut self.screen_buffer,
-                &mut self.obj_library,
-                &self.sprites,
-            )?;
+            s.remove_all(&mut self.screen_buffer, &mut self.obj_library)?;
             s.change_screen_area_contents(&mut self.obj_library, &self.sprites);
             let ids = s.draw_objects.to_vec();
             let screen_rect = s.rect();
@@ -250,7 +313,7 @@ where
             M::refresh(self)?;
             Ok(())
         } else {
-            Err(DrawError::DisplayKeyNotFound(screen_id))
+            Err(self.missing_screen_error(screen_id))
         }
     }
 
@@ -258,10 +321,38 @@ where
         &mut self,
         screen_id: ScreenKey,
         callback: Box<dyn FnMut() + 'static + Send>,
-    ) {
+    ) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&screen_id) {
             s.add_on_screen_select(callback);
+            Ok(())
+        } else {
+            Err(self.missing_screen_error(screen_id))
+        }
+    }
+
+    /// Purge the objects quarantined since the last call from the buffer.
+    /// returns an error describing the panic of each of them
+    pub fn take_quarantined(&mut self) -> Result<Vec<DrawError>, DrawError> {
+        let panics = self.obj_library.take_panics();
+        if panics.is_empty() {
+            return Ok(Vec::new());
         }
+        let mut errors = Vec::with_capacity(panics.len());
+        for (key, message) in panics {
+            self.screen_buffer.remove_from_buffer(key.object_id);
+            errors.push(DrawError::DrawablePanicked {
+                screen_id: key.screen_id,
+                obj_id: key.object_id,
+                message,
+            });
+        }
+        M::refresh(self)?;
+        Ok(errors)
+    }
+
+    /// Errors of the drawer thread, that happened since the last call
+    pub fn take_drawer_errors(&self) -> Vec<DrawError> {
+        self.screen_buffer.take_drawer_errors()
     }
     fn on_screen_select(&mut self, screen_id: ScreenKey) -> Result<(), AppError> {
         if let Some(s) = self.screens.get_mut(&screen_id) {
@@ -277,11 +368,7 @@ where
         new_layer: usize,
     ) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&screen_id) {
-            s.remove_all(
-                &mut self.screen_buffer,
-                &mut self.obj_library,
-                &self.sprites,
-            )?;
+            s.remove_all(&mut self.screen_buffer, &mut self.obj_library)?;
             s.change_screen_layer(new_layer);
             s.render_all(
                 &mut self.screen_buffer,
@@ -291,7 +378,7 @@ where
             M::refresh(self)?;
             Ok(())
         } else {
-            Err(DrawError::DisplayKeyNotFound(screen_id))
+            Err(self.missing_screen_error(screen_id))
         }
     }
 
@@ -299,9 +386,11 @@ where
     pub fn register_drawable(
         &mut self,
         screen_id: ScreenKey,
-        obj: DrawObject,
+        mut obj: DrawObject,
     ) -> Result<DrawObjectKey, DrawError> {
         if let Some(s) = self.screens.get_mut(&screen_id) {
+            obj.creation_time = self.clock.now();
+            obj.drawable.attach_clock(self.clock.clone());
             let new_obj_id = self.obj_library.add_obj(screen_id, obj);
             s.register_drawable(new_obj_id, &self.obj_library);
             let area = s.rect();
@@ -319,35 +408,41 @@ where
             M::render_screen(self, key.screen_id)?;
             Ok(key)
         } else {
-            Err(DrawError::DisplayKeyNotFound(screen_id))
+            Err(self.missing_screen_error(screen_id))
         }
     }
 
     pub fn remove_drawable(&mut self, id: DrawObjectKey) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&id.screen_id) {
-            s.remove_drawable(
-                id.object_id,
-                &mut self.screen_buffer,
-                &mut self.obj_library,
-                &self.sprites,
-            )?;
+            s.remove_drawable(id.object_id, &mut self.screen_buffer, &mut self.obj_library)?;
             M::refresh(self)?;
             Ok(())
         } else {
-            Err(DrawError::DisplayKeyNotFound(id.screen_id))
+            Err(self.missing_screen_error(id.screen_id))
         }
     }
 
+    /// Remove a drawable from its screen, while keeping it in the object library.
+    /// Its key stays valid, so render_drawable shows it again.
+    /// Objects, whose lifetime ended, are hidden the same way
     pub fn explicit_remove_drawable(&mut self, id: &DrawObjectKey) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&id.screen_id) {
             s.deregister_drawable(id.object_id);
             self.remove_drawable(*id)?;
             Ok(())
         } else {
-            Err(DrawError::DisplayKeyNotFound(id.screen_id))
+            Err(self.missing_screen_error(id.screen_id))
         }
     }
 
+    /// Remove a drawable from its screen and from the object library.
+    /// Its key becomes stale, so later calls using it fail instead of touching another object
+    pub fn destroy_drawable(&mut self, id: DrawObjectKey) -> Result<(), DrawError> {
+        self.explicit_remove_drawable(&id)?;
+        self.obj_library.remove_obj(&id);
+        Ok(())
+    }
+
     pub fn replace_drawable(
         &mut self,
         id: DrawObjectKey,
@@ -357,7 +452,7 @@ where
             if let Some(s) = self.screens.get_mut(&id.screen_id) {
                 s.deregister_drawable(id.object_id);
             } else {
-                return Err(DrawError::DisplayKeyNotFound(id.screen_id));
+                return Err(self.missing_screen_error(id.screen_id));
             }
         }
 
@@ -365,6 +460,8 @@ where
 
         if let Some(obj) = self.obj_library.get_mut(&id) {
             obj.drawable = drawable;
+            obj.drawable.attach_clock(self.clock.clone());
+            self.obj_library.release(&id);
         }
 
         {
@@ -382,10 +479,103 @@ where
         Ok(())
     }
 
+    /// Apply all operations of the batch, while only compositing and refreshing once at the end.
+    /// returns the result of every single operation
+    pub fn apply_batch(
+        &mut self,
+        batch: RenderBatch,
+    ) -> Result<Vec<Result<BatchOutcome, AppError>>, DrawError> {
+        self.batch_dirty_screens = Some(HashSet::new());
+        let results = batch
+            .into_operations()
+            .into_iter()
+            .map(|operation| self.apply_batch_operation(operation))
+            .collect();
+
+        let dirty_screens = self.batch_dirty_screens.take().unwrap_or_default();
+        for screen_id in dirty_screens {
+            if let Some(s) = self.screens.get_mut(&screen_id) {
+                s.render_all(
+                    &mut self.screen_buffer,
+                    &mut self.obj_library,
+                    &self.sprites,
+                )?;
+            }
+        }
+        M::refresh(self)?;
+        Ok(results)
+    }
+
+    fn apply_batch_operation(
+        &mut self,
+        operation: BatchOperation,
+    ) -> Result<BatchOutcome, AppError> {
+        match operation {
+            BatchOperation::RegisterDrawable { screen_id, obj } => {
+                let key = self.register_drawable(screen_id, obj)?;
+                return Ok(BatchOutcome::Registered(key));
+            }
+            BatchOperation::RemoveDrawable { id } => self.remove_drawable(id)?,
+            BatchOperation::ExplicitRemoveDrawable { id } => self.explicit_remove_drawable(&id)?,
+            BatchOperation::DestroyDrawable { id } => self.destroy_drawable(id)?,
+            BatchOperation::ReplaceDrawable { id, drawable } => {
+                self.replace_drawable(id, drawable)?
+            }
+            BatchOperation::RenderDrawable { key } => self.render_drawable(key)?,
+            BatchOperation::UpdateDrawable { key, update } => self.update_drawable(key, update)?,
+            BatchOperation::MoveDrawableTo { handle, new_pos } => {
+                self.move_drawable_to(handle, new_pos)?
+            }
+            BatchOperation::MoveDrawableBy { handle, dx, dy } => {
+                self.move_drawable_by(handle, dx, dy)?
+            }
+            BatchOperation::MoveDrawablePoint {
+                handle,
+                point_index,
+                new_pos,
+            } => self.move_drawable_point(handle, point_index, new_pos)?,
+            BatchOperation::ReplaceDrawablePoints { handle, new_points } => {
+                self.replace_drawable_points(handle, new_points)?
+            }
+        }
+        Ok(BatchOutcome::Applied)
+    }
+
+    /// Apply an update to a drawable and only re-render that object
+    pub fn update_drawable(
+        &mut self,
+        key: DrawObjectKey,
+        update: DrawableUpdate,
+    ) -> Result<(), DrawError> {
+        let Some(s) = self.screens.get_mut(&key.screen_id) else {
+            return Err(self.missing_screen_error(key.screen_id));
+        };
+        s.remove_drawable(
+            key.object_id,
+            &mut self.screen_buffer,
+            &mut self.obj_library,
+        )?;
+
+        let result = match self.obj_library.get_mut(&key) {
+            Some(obj) => update(&mut *obj.drawable),
+            None => Err(self.obj_library.missing_key_error(&key)),
+        };
+
+        // the object gets drawn again, even if the update failed, since it has already been removed
+        s.render_drawable(
+            key.object_id,
+            &mut self.screen_buffer,
+            &mut self.obj_library,
+            &self.sprites,
+        )?;
+        M::refresh(self)?;
+        result
+    }
+
     /// checks if any of the currently existing drawobjects should be removed,
     /// because its duration on screen has ended
     pub fn check_if_object_lifetime_ended(&mut self) -> Result<(), DrawError> {
-        let now = std::time::Instant::now();
+        let now = self.clock.now();
         let mut expired_keys = Vec::new();
 
         for (screen_id, screen) in &self.screens {
@@ -411,6 +601,12 @@ where
         Ok(())
     }
 
+    /// Register an already loaded video as a sprite,
+    /// for example one produced by stop_recording
+    pub fn register_sprite(&mut self, video: AsciiVideo) -> SpriteId {
+        self.sprites.add(SpriteEntry { info: video })
+    }
+
     /// Load a sprite from an ASCII video file.
     pub fn register_sprite_from_source(&mut self, path: &str) -> Result<SpriteId, AppError> {
         let video = AsciiVideo::read_from_file(path)?;
@@ -421,6 +617,9 @@ where
     }
 
     pub fn render_screen(&mut self, screen_id: ScreenKey) -> Result<(), AppError> {
+        if !self.screens.contains_key(&screen_id) {
+            return Err(self.missing_screen_error(screen_id).into());
+        }
         M::render_screen(self, screen_id)?;
         Ok(())
     }
@@ -429,7 +628,9 @@ where
     pub fn render_drawable(&mut self, object_key: DrawObjectKey) -> Result<(), DrawError> {
         if let Some(s) = self.screens.get_mut(&object_key.screen_id) {
             if let Some(obj) = self.obj_library.get_mut(&object_key) {
-                obj.creation_time = std::time::Instant::now()
+                obj.creation_time = self.clock.now()
+            } else {
+                return Err(self.obj_library.missing_key_error(&object_key));
             }
 
             s.register_drawable(object_key.object_id, &self.obj_library);
@@ -437,17 +638,144 @@ where
             M::render_screen(self, object_key.screen_id)?;
             Ok(())
         } else {
-            Err(DrawError::DisplayKeyNotFound(object_key.screen_id))
+            Err(self.missing_screen_error(object_key.screen_id))
         }
     }
 
     fn forced_refresh(&mut self) -> Result<(), DrawError> {
+        // screens could have moved, so the cursor is placed again before the flush
+        self.sync_cursor();
         B::update_terminal(&mut self.screen_buffer, self.update_interval_expand_amount)?;
+        self.record_frame()?;
+        Ok(())
+    }
+
+    /// Show the cursor, once it has been placed with set_cursor_position
+    pub fn show_cursor(&mut self) {
+        self.cursor.visible = true;
+        self.sync_cursor();
+    }
+
+    pub fn hide_cursor(&mut self) {
+        self.cursor.visible = false;
+        self.sync_cursor();
+    }
+
+    /// Place the cursor at a position relative to the top left corner of the screen.
+    /// The cursor stays hidden, while the position lies outside of the screen
+    pub fn set_cursor_position(
+        &mut self,
+        screen_id: ScreenKey,
+        pos: Point<i32>,
+    ) -> Result<(), DrawError> {
+        if !self.screens.contains_key(&screen_id) {
+            return Err(self.missing_screen_error(screen_id));
+        }
+        self.cursor_anchor = Some((screen_id, pos));
+        self.sync_cursor();
+        Ok(())
+    }
+
+    pub fn set_cursor_shape(&mut self, shape: CursorShape, blinking: bool) {
+        self.cursor.shape = shape;
+        self.cursor.blinking = blinking;
+        self.sync_cursor();
+    }
+
+    /// Print lines above an inline region, where they scroll away like normal output
+    pub fn print_above(&self, text: String) {
+        self.screen_buffer.print_above(text);
+    }
+
+    /// The cursor state with its absolute position,
+    /// hidden if its screen is gone or the position lies outside of it
+    fn resolve_cursor(&self) -> CursorState {
+        let mut resolved = self.cursor;
+        let anchor = self.cursor_anchor.and_then(|(screen_id, pos)| {
+            let rect = self.screens.get(&screen_id)?.rect();
+            let abs = rect.p1 + pos;
+            let inside = abs.x >= rect.p1.x.max(0)
+                && abs.y >= rect.p1.y.max(0)
+                && abs.x < rect.p2.x.min(self.terminal_size.0 as i32)
+                && abs.y < rect.p2.y.min(self.terminal_size.1 as i32);
+            inside.then_some((abs.x as u16, abs.y as u16))
+        });
+        match anchor {
+            Some(position) => resolved.position = position,
+            None => resolved.visible = false,
+        }
+        resolved
+    }
+
+    /// hand the cursor state to the drawer, if it changed
+    fn sync_cursor(&mut self) {
+        let resolved = self.resolve_cursor();
+        if self.sent_cursor != Some(resolved) {
+            self.screen_buffer.set_cursor(resolved);
+            self.sent_cursor = Some(resolved);
+        }
+    }
+
+    /// Start recording every flushed frame, replacing the current recording
+    pub fn start_recording(&mut self, area: RecordingArea) -> Result<(), DrawError> {
+        if let RecordingArea::Screen(screen_id) = area
+            && !self.screens.contains_key(&screen_id)
+        {
+            return Err(self.missing_screen_error(screen_id));
+        }
+        self.recorder = Some(FrameRecorder::new(area));
         Ok(())
     }
 
+    /// Stop the recording and bake the recorded frames into a video
+    pub fn stop_recording(&mut self) -> Result<AsciiVideo, DrawError> {
+        self.recorder
+            .take()
+            .ok_or(DrawError::NotRecording)?
+            .into_video()
+    }
+
+    fn record_frame(&mut self) -> Result<(), DrawError> {
+        let Some(recorder) = &mut self.recorder else {
+            return Ok(());
+        };
+        let size = self.terminal_size;
+        let rect = match recorder.area() {
+            RecordingArea::FullScreen => AreaRect::FullScreen.area_to_rect(&size),
+            RecordingArea::Screen(screen_id) => match self.screens.get(&screen_id) {
+                Some(screen) => screen.rect(),
+                // the screen has been destroyed during the recording
+                None => return Ok(()),
+            },
+            RecordingArea::Rect(rect) => rect,
+        };
+        recorder.record(self.screen_buffer.front_buffer(), size, rect)
+    }
+
+    /// The content of the last flush, if the drawer is headless.
+    /// Waits for the drawer to handle all pending commands first
+    pub fn snapshot(&self) -> Result<Option<TerminalContentInformation>, DrawError> {
+        self.screen_buffer.wait_for_drawer()?;
+        Ok(self.screen_buffer.capture().and_then(|c| c.latest()))
+    }
+
+    /// The composed content of all screens, as it would be drawn by the next flush
+    pub fn composed_content(&self) -> TerminalContentInformation {
+        self.screen_buffer.composed_content()
+    }
+
+    /// All frames kept by a headless drawer, the oldest first
+    pub fn frame_history(&self) -> Result<Vec<TerminalContentInformation>, DrawError> {
+        self.screen_buffer.wait_for_drawer()?;
+        Ok(self
+            .screen_buffer
+            .capture()
+            .map(|c| c.history())
+            .unwrap_or_default())
+    }
+
     pub fn handle_resize(&mut self, new_size: (u16, u16)) -> Result<(), DrawError> {
-        self.screen_buffer = B::new(new_size);
+        self.screen_buffer.resize(new_size);
         self.terminal_size = new_size;
         for screen in self.screens.values_mut() {
             screen.terminal_size = self.terminal_size
@@ -458,12 +786,17 @@ where
     }
 
     /// Generate a unique screen key.
-    pub fn generate_screen_key(&self) -> ScreenKey {
-        let mut id = self.screens.len();
-        while self.screens.contains_key(&id) {
-            id = id.saturating_add(1);
+    pub fn generate_screen_key(&mut self) -> ScreenKey {
+        self.screen_ids.allocate()
+    }
+
+    /// the error to return, when no screen exists for the given key
+    fn missing_screen_error(&self, screen_id: ScreenKey) -> DrawError {
+        if self.screen_ids.is_stale(screen_id) {
+            DrawError::StaleScreenKey(screen_id)
+        } else {
+            DrawError::DisplayKeyNotFound(screen_id)
         }
-        id
     }
 
     pub fn move_drawable_to(
@@ -633,13 +966,10 @@ where
         self.remove_drawable(object_key)?;
 
         let result = {
-            let obj =
-                self.obj_library
-                    .get_mut(&object_key)
-                    .ok_or(DrawError::DrawableHandleNotFound {
-                        screen_id: object_key.screen_id,
-                        obj_id: object_key.object_id,
-                    })?;
+            let obj = match self.obj_library.all_objects.get_mut(&object_key) {
+                Some(obj) => obj,
+                None => return Err(self.obj_library.missing_key_error(&object_key)),
+            };
 
             update_fn(&mut *obj.drawable)
         };
@@ -674,7 +1004,7 @@ where
 
             let screen_selected_char =
                 if let Some(drawn_idx) = self.screen_buffer.idx_of(mouse_point) {
-                    let drawn = &self.screen_buffer.cell_info_mut()[drawn_idx];
+                    let drawn = &self.screen_buffer.cell_info()[drawn_idx];
                     if let Some((obj_id, _)) = B::get_char_to_write(drawn)
                         && let Some(key) = self.obj_library.get_obj_screen(obj_id)
                     {
@@ -697,21 +1027,22 @@ where
                                     && let MouseMessage::Pressed(_) = msg
                                 {
                                     // find the highest screen at that position
-                                    let mut current_highest_screen = (usize::MAX, 0);
+                                    let mut current_highest_screen: Option<(ScreenKey, usize)> =
+                                        None;
                                     for (id, screen) in &self.screens {
                                         let screen_layer = screen.layer();
                                         if screen.rect().contains(mouse_point)
-                                            && screen_layer >= current_highest_screen.1
+                                            && current_highest_screen
+                                                .is_none_or(|(_, layer)| screen_layer >= layer)
                                             && id == screen_selected_char
                                         {
-                                            current_highest_screen = (*id, screen_layer);
+                                            current_highest_screen = Some((*id, screen_layer));
                                         }
                                     }
 
-                                    if self.screens.contains_key(&current_highest_screen.0) {
-                                        Some(TargetScreen::Screen(current_highest_screen.0))
-                                    } else {
-                                        Some(TargetScreen::None)
+                                    match current_highest_screen {
+                                        Some((id, _)) => Some(TargetScreen::Screen(id)),
+                                        None => Some(TargetScreen::None),
                                     }
                                 } else if let SubscriptionMessage::Resize(x, y) = m {
                                     resize_required = (true, (x, y));
@@ -752,12 +1083,19 @@ where
     pub fn into_buffered(self) -> Renderer<B, M> {
         Renderer {
             screens: self.screens,
+            screen_ids: self.screen_ids,
             obj_library: self.obj_library,
             screen_buffer: self.screen_buffer,
             sprites: self.sprites,
             update_interval_expand_amount: self.update_interval_expand_amount,
             terminal_size: self.terminal_size,
             screen_select_handler: self.screen_select_handler,
+            batch_dirty_screens: self.batch_dirty_screens,
+            clock: self.clock,
+            recorder: self.recorder,
+            cursor: self.cursor,
+            cursor_anchor: self.cursor_anchor,
+            sent_cursor: self.sent_cursor,
             _mode: std::marker::PhantomData,
         }
     }
@@ -765,12 +1103,19 @@ where
     pub fn into_instant(self) -> Renderer<B, M> {
         Renderer {
             screens: self.screens,
+            screen_ids: self.screen_ids,
             obj_library: self.obj_library,
             screen_buffer: self.screen_buffer,
             sprites: self.sprites,
             update_interval_expand_amount: self.update_interval_expand_amount,
             terminal_size: self.terminal_size,
             screen_select_handler: self.screen_select_handler,
+            batch_dirty_screens: self.batch_dirty_screens,
+            clock: self.clock,
+            recorder: self.recorder,
+            cursor: self.cursor,
+            cursor_anchor: self.cursor_anchor,
+            sent_cursor: self.sent_cursor,
             _mode: std::marker::PhantomData,
         }
     }
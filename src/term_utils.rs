@@ -1,48 +1,103 @@
-#[cfg(unix)]
+use crate::rendering::panic_isolation::panic_is_isolated;
 use crossterm::event::{
-    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange,
+    KeyboardEnhancementFlags,
 };
+#[cfg(unix)]
+use crossterm::event::{PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags};
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
+    cursor::{Hide, MoveTo, SetCursorStyle, Show, position},
     event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
+    execute, queue,
     terminal::{
         Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
-        enable_raw_mode,
+        enable_raw_mode, size,
     },
 };
-use std::io::{Result, stdout};
+use std::fmt;
+use std::io::{Result, Write, stdout};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// Rows of the normal screen, that an inline renderer draws into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InlineRegion {
+    /// terminal row of the top of the region
+    pub origin: u16,
+    pub height: u16,
+    /// rows of the whole terminal
+    pub terminal_rows: u16,
+}
 
+/// Take over the terminal with TerminalConfig::default, until the end of the enclosing scope.
+///
+/// On ctrl-c the terminal gets restored and the process exits with 130,
+/// pass a closure to run instead of exiting
 #[macro_export]
 macro_rules! init_terminal {
     () => {
-        $crate::initial_terminal_state().unwrap();
-        use $crate::restore_terminal;
-        ctrlc::set_handler(|| {
-            let _ = restore_terminal();
-            std::process::exit(130);
-        })
-        .expect("Error setting Ctrl-C handler");
-
-        let prev_hook = std::panic::take_hook();
-        std::panic::set_hook(Box::new(move |panic_info| {
-            let _ = restore_terminal();
-            prev_hook(panic_info);
-        }));
+        $crate::init_terminal!(|| std::process::exit(130));
+    };
+    ($on_interrupt:expr) => {
+        let _terminal_guard = $crate::TerminalConfig::default()
+            .on_interrupt($on_interrupt)
+            .enter()
+            .expect("Error taking over the terminal");
     };
 }
 
+/// Put the terminal into the state of TerminalConfig::default.
+/// Undo it with restore_terminal
 pub fn initial_terminal_state() -> Result<()> {
+    let config = TerminalConfig::default();
+    if config.raw_mode {
+        enable_raw_mode()?;
+    }
+    config.write_setup(&mut stdout())
+}
+pub fn restore_terminal() -> Result<()> {
+    let mut stdout = stdout();
+    #[cfg(unix)]
+    execute!(stdout, PopKeyboardEnhancementFlags)?;
+    execute!(
+        stdout,
+        Clear(ClearType::All),
+        SetCursorStyle::DefaultUserShape,
+        Show,
+        MoveTo(0, 0),
+        LeaveAlternateScreen
+    )?;
+    execute!(stdout, DisableMouseCapture)?;
+    disable_raw_mode()?;
+    Ok(())
+}
+
+/// Reserve lines below the cursor for an inline renderer, instead of entering the alternate screen.
+///
+/// The screen scrolls, if the lines do not fit below the cursor.
+/// At least one row above the region is kept, for text printed above it.
+/// Pass the region to CrosstermOptions::inline and create the renderer with its height
+pub fn initial_inline_state(height: u16) -> Result<InlineRegion> {
     let mut stdout = stdout();
 
     enable_raw_mode()?;
 
+    let (_, rows) = size()?;
+    let height = height.clamp(1, rows.saturating_sub(1).max(1));
+    if position()?.0 != 0 {
+        write!(stdout, "\r\n")?;
+    }
+    write!(stdout, "{}", "\r\n".repeat(height as usize - 1))?;
+    stdout.flush()?;
+    let origin = (position()?.1 + 1).saturating_sub(height);
+
     execute!(
         stdout,
-        EnterAlternateScreen,
-        Clear(ClearType::All),
-        Hide,
-        EnableMouseCapture,
+        MoveTo(0, origin),
+        Clear(ClearType::FromCursorDown),
+        Hide
     )?;
 
     #[cfg(unix)]
@@ -55,21 +110,268 @@ pub fn initial_terminal_state() -> Result<()> {
         )
     )?;
 
-    Ok(())
+    Ok(InlineRegion {
+        origin,
+        height,
+        terminal_rows: rows,
+    })
 }
-pub fn restore_terminal() -> Result<()> {
-    println!("restoring terminal");
+
+/// Undo initial_inline_state, without clearing anything.
+/// Call it after the renderer has been dropped, which leaves the cursor below the last frame
+pub fn restore_inline_terminal() -> Result<()> {
     let mut stdout = stdout();
     #[cfg(unix)]
     execute!(stdout, PopKeyboardEnhancementFlags)?;
-    execute!(
-        stdout,
-        Clear(ClearType::All),
-        Show,
-        MoveTo(0, 0),
-        LeaveAlternateScreen
-    )?;
-    execute!(stdout, DisableMouseCapture)?;
+    execute!(stdout, SetCursorStyle::DefaultUserShape, Show)?;
     disable_raw_mode()?;
     Ok(())
 }
+
+/// Selects, what gets enabled when taking over the terminal.
+///
+/// The default matches initial_terminal_state: raw mode, the alternate screen, a hidden cursor,
+/// mouse capture and keyboard enhancements on unix.
+/// Enter it to get a TerminalGuard, that restores exactly what has been enabled
+#[derive(Clone)]
+pub struct TerminalConfig {
+    pub raw_mode: bool,
+    pub alternate_screen: bool,
+    pub hide_cursor: bool,
+    pub mouse_capture: bool,
+    /// pushed onto the keyboard enhancement stack on unix, if not empty
+    pub keyboard_enhancement: KeyboardEnhancementFlags,
+    pub bracketed_paste: bool,
+    pub focus_reporting: bool,
+    pub restore_on_panic: bool,
+    on_interrupt: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl fmt::Debug for TerminalConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TerminalConfig")
+            .field("raw_mode", &self.raw_mode)
+            .field("alternate_screen", &self.alternate_screen)
+            .field("hide_cursor", &self.hide_cursor)
+            .field("mouse_capture", &self.mouse_capture)
+            .field("keyboard_enhancement", &self.keyboard_enhancement)
+            .field("bracketed_paste", &self.bracketed_paste)
+            .field("focus_reporting", &self.focus_reporting)
+            .field("restore_on_panic", &self.restore_on_panic)
+            .field("on_interrupt", &self.on_interrupt.is_some())
+            .finish()
+    }
+}
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        TerminalConfig {
+            raw_mode: true,
+            alternate_screen: true,
+            hide_cursor: true,
+            mouse_capture: true,
+            keyboard_enhancement: KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES,
+            bracketed_paste: false,
+            focus_reporting: false,
+            restore_on_panic: true,
+            on_interrupt: None,
+        }
+    }
+}
+
+impl TerminalConfig {
+    /// a config, that leaves the terminal untouched
+    pub fn none() -> Self {
+        TerminalConfig {
+            raw_mode: false,
+            alternate_screen: false,
+            hide_cursor: false,
+            mouse_capture: false,
+            keyboard_enhancement: KeyboardEnhancementFlags::empty(),
+            bracketed_paste: false,
+            focus_reporting: false,
+            restore_on_panic: false,
+            on_interrupt: None,
+        }
+    }
+
+    pub fn raw_mode(mut self, raw_mode: bool) -> Self {
+        self.raw_mode = raw_mode;
+        self
+    }
+
+    pub fn alternate_screen(mut self, alternate_screen: bool) -> Self {
+        self.alternate_screen = alternate_screen;
+        self
+    }
+
+    pub fn hide_cursor(mut self, hide_cursor: bool) -> Self {
+        self.hide_cursor = hide_cursor;
+        self
+    }
+
+    pub fn mouse_capture(mut self, mouse_capture: bool) -> Self {
+        self.mouse_capture = mouse_capture;
+        self
+    }
+
+    pub fn keyboard_enhancement(mut self, flags: KeyboardEnhancementFlags) -> Self {
+        self.keyboard_enhancement = flags;
+        self
+    }
+
+    pub fn bracketed_paste(mut self, bracketed_paste: bool) -> Self {
+        self.bracketed_paste = bracketed_paste;
+        self
+    }
+
+    pub fn focus_reporting(mut self, focus_reporting: bool) -> Self {
+        self.focus_reporting = focus_reporting;
+        self
+    }
+
+    /// Restore the terminal before the panic message gets printed.
+    /// Panics of drawables, that the renderer isolates, leave it as it is
+    pub fn restore_on_panic(mut self, restore_on_panic: bool) -> Self {
+        self.restore_on_panic = restore_on_panic;
+        self
+    }
+
+    /// Install a ctrl-c handler, that restores the terminal and then calls the hook,
+    /// instead of leaving the process in a broken terminal.
+    /// In raw mode ctrl-c arrives as a key event instead, so this only fires without it
+    pub fn on_interrupt(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_interrupt = Some(Arc::new(hook));
+        self
+    }
+
+    /// Apply the config to the terminal on stdout.
+    /// Fails, if a ctrl-c handler has already been installed in this process
+    pub fn enter(self) -> Result<TerminalGuard> {
+        if self.raw_mode {
+            enable_raw_mode()?;
+        }
+        let active = Arc::new(AtomicBool::new(true));
+        let guard = TerminalGuard {
+            config: self.clone(),
+            active: active.clone(),
+        };
+        self.write_setup(&mut stdout())?;
+
+        if self.restore_on_panic {
+            let config = self.clone();
+            let active = active.clone();
+            let prev_hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |panic_info| {
+                // the renderer survives panics of drawables, so the terminal is kept
+                if !panic_is_isolated() {
+                    let _ = restore_once(&config, &active);
+                }
+                prev_hook(panic_info);
+            }));
+        }
+        if let Some(hook) = self.on_interrupt.clone() {
+            let config = self.clone();
+            ctrlc::set_handler(move || {
+                let _ = restore_once(&config, &active);
+                hook();
+            })
+            .map_err(std::io::Error::other)?;
+        }
+        Ok(guard)
+    }
+
+    /// Write the escape sequences enabling the config, everything except raw mode.
+    /// Also usable for terminals behind other writers, like a pty or a socket
+    pub fn write_setup(&self, out: &mut impl Write) -> Result<()> {
+        if self.alternate_screen {
+            queue!(out, EnterAlternateScreen, Clear(ClearType::All))?;
+        }
+        if self.hide_cursor {
+            queue!(out, Hide)?;
+        }
+        if self.mouse_capture {
+            queue!(out, EnableMouseCapture)?;
+        }
+        if self.bracketed_paste {
+            queue!(out, EnableBracketedPaste)?;
+        }
+        if self.focus_reporting {
+            queue!(out, EnableFocusChange)?;
+        }
+        #[cfg(unix)]
+        if !self.keyboard_enhancement.is_empty() {
+            queue!(out, PushKeyboardEnhancementFlags(self.keyboard_enhancement))?;
+        }
+        out.flush()
+    }
+
+    /// Write the escape sequences undoing write_setup, in reverse order
+    pub fn write_teardown(&self, out: &mut impl Write) -> Result<()> {
+        #[cfg(unix)]
+        if !self.keyboard_enhancement.is_empty() {
+            queue!(out, PopKeyboardEnhancementFlags)?;
+        }
+        if self.focus_reporting {
+            queue!(out, DisableFocusChange)?;
+        }
+        if self.bracketed_paste {
+            queue!(out, DisableBracketedPaste)?;
+        }
+        if self.mouse_capture {
+            queue!(out, DisableMouseCapture)?;
+        }
+        if self.hide_cursor {
+            queue!(out, SetCursorStyle::DefaultUserShape, Show)?;
+        }
+        if self.alternate_screen {
+            queue!(out, LeaveAlternateScreen)?;
+        }
+        out.flush()
+    }
+}
+
+/// Restores the terminal to the state before TerminalConfig::enter, when dropped
+#[derive(Debug)]
+pub struct TerminalGuard {
+    config: TerminalConfig,
+    /// cleared by the first restore, shared with the panic and ctrl-c handlers
+    active: Arc<AtomicBool>,
+}
+
+impl TerminalGuard {
+    pub fn config(&self) -> &TerminalConfig {
+        &self.config
+    }
+
+    /// Restore the terminal now, instead of on drop. Later calls do nothing
+    pub fn restore(&self) -> Result<()> {
+        restore_once(&self.config, &self.active)
+    }
+
+    /// Whether the terminal has already been restored, by restore or by one of the handlers
+    pub fn is_restored(&self) -> bool {
+        !self.active.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.restore() {
+            log::error!("Failed to restore the terminal: {}", e);
+        }
+    }
+}
+
+fn restore_once(config: &TerminalConfig, active: &AtomicBool) -> Result<()> {
+    if !active.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    config.write_teardown(&mut stdout())?;
+    if config.raw_mode {
+        disable_raw_mode()?;
+    }
+    Ok(())
+}
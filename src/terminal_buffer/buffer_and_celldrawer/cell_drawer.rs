@@ -1,13 +1,23 @@
 use crate::{
     DrawError,
-    terminal_buffer::buffer_and_celldrawer::{BatchDrawInfo, screen_buffer::CellDrawerCommand},
+    terminal_buffer::{
+        CursorState,
+        buffer_and_celldrawer::{
+            BatchDrawInfo, standard_celldrawer::test_celldrawer::FrameCapture,
+        },
+    },
 };
 
-use std::{fmt::Debug, sync::mpsc::Receiver};
+use std::fmt::Debug;
 
 /// Trait that describes how to write a string of chars
+///
+/// The drawer gets created on the thread of the ScreenBuffer and is then moved to its drawer thread,
+/// which receives the commands and calls the functions below.
 pub trait CellDrawer: Debug {
-    fn init(rx: Receiver<CellDrawerCommand>) -> Self;
+    fn init() -> Self
+    where
+        Self: Sized;
 
     /// Write a string at an absolute position.
     /// also used for drawing single character
@@ -16,5 +26,18 @@ pub trait CellDrawer: Debug {
     /// Flush any buffered output to the terminal, or any other output that you might prefer
     fn flush(&mut self) -> Result<(), DrawError>;
 
-    fn recv(&self) -> Result<CellDrawerCommand, std::sync::mpsc::RecvError>;
+    /// Replace the state of the hardware cursor.
+    /// Drawers writing to a terminal apply it right away and again after every flush,
+    /// since drawing moves the cursor
+    fn set_cursor(&mut self, _cursor: CursorState) {}
+
+    /// Print lines above the drawn content, so they scroll away like normal output.
+    /// Only drawers rendering into an inline region support this, others ignore it
+    fn print_above(&mut self, _text: &str) {}
+
+    /// Handle to the frames captured by a headless drawer.
+    /// Drawers writing to a real terminal return None
+    fn capture(&self) -> Option<FrameCapture> {
+        None
+    }
 }
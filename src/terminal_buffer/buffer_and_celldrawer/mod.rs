@@ -15,5 +15,9 @@ pub use screen_buffer_core::ScreenBufferCore;
 
 pub mod standard_celldrawer;
 pub use standard_celldrawer::{
-    crossterm_celldrawer::CrosstermCellDrawer, test_celldrawer::TestCellDrawer,
+    asciicast_celldrawer::AsciicastCellDrawer,
+    color_mode::ColorMode,
+    crossterm_celldrawer::{CrosstermCellDrawer, CrosstermOptions},
+    tee_celldrawer::{SinkFailure, TeeCellDrawer},
+    test_celldrawer::TestCellDrawer,
 };
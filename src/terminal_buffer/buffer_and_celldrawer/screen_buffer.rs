@@ -1,13 +1,17 @@
 use crate::{
     BasicDraw, DrawError, DrawObject, ObjectId, SpriteRegistry, UpdateInterval,
-    terminal_buffer::{CellDrawer, CharacterInfo, CharacterInfoList, ScreenBufferCore},
-    update_interval_handler::UpdateIntervalCreator,
+    terminal_buffer::{
+        CellAttributes, CellDrawer, CharacterInfo, CharacterInfoList, CursorState,
+        ScreenBufferCore, StyledChar,
+        buffer_and_celldrawer::standard_celldrawer::test_celldrawer::{
+            FrameCapture, TerminalContentInformation,
+        },
+        compositing,
+    },
+    update_interval_handler::{UpdateIntervalCreator, UpdateIntervalType},
 };
 use common_stdx::Rect;
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Debug,
-};
+use std::{collections::HashMap, fmt::Debug, sync::mpsc};
 
 use ascii_assets::Color;
 use ascii_assets::TerminalChar;
@@ -17,14 +21,20 @@ use common_stdx::Point;
 pub enum CellDrawerCommand {
     SetString(BatchDrawInfo, (u16, u16)),
     Flush,
+    /// answered by the drawer thread, once all previous commands have been handled
+    Barrier(mpsc::Sender<()>),
+    SetCursor(CursorState),
+    PrintAbove(String),
     Stop,
 }
 
 #[derive(Debug, Clone)]
 pub struct BatchSegment {
+    /// whole grapheme clusters, wide ones take up two cells
     pub text: String,
     pub fg_color: Option<Color>,
     pub bg_color: Option<Color>,
+    pub attrs: CellAttributes,
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +44,28 @@ pub struct BatchDrawInfo {
     pub segments: Vec<BatchSegment>,
 }
 
+impl BatchDrawInfo {
+    /// Append a char to the batch, starting a new segment if its colors or attributes differ from the last one
+    pub fn push_char(&mut self, chr: &StyledChar) {
+        if let Some(seg) = self.segments.last_mut()
+            && seg.fg_color == chr.chr.fg_color
+            && seg.bg_color == chr.chr.bg_color
+            && seg.attrs == chr.attrs
+        {
+            chr.push_to(&mut seg.text);
+            return;
+        }
+        let mut text = String::new();
+        chr.push_to(&mut text);
+        self.segments.push(BatchSegment {
+            text,
+            fg_color: chr.chr.fg_color,
+            bg_color: chr.chr.bg_color,
+            attrs: chr.attrs,
+        });
+    }
+}
+
 pub trait ScreenBuffer: ScreenBufferCore {
     type Drawer: CellDrawer + Send + 'static;
     fn new(size: (u16, u16)) -> Self
@@ -60,19 +92,19 @@ pub trait ScreenBuffer: ScreenBufferCore {
             Point { x: 0, y: 0 }
         };
         let opt_c: Option<UpdateIntervalCreator> = drawable.bounding_iv(sprites);
-        // TODO: is this right??
-        let update_intervals: HashMap<u16, Vec<UpdateInterval>> =
-            self.handle_none_interval_creator(opt_c, bounds.p1);
+        self.handle_none_interval_creator(opt_c, bounds.p1);
 
         let size = drawable.size(sprites)?;
 
-        let mut touched: HashSet<usize> = HashSet::new();
+        let mut placed = Vec::with_capacity(draws.len());
 
         for unshifted_bd in draws.iter_mut() {
             // using the top left corner of the screen, to shift the drawables position on screen
             let mut rd = BasicDraw {
                 pos: unshifted_bd.pos + bounds.p1,
                 chr: unshifted_bd.chr,
+                attrs: unshifted_bd.attrs,
+                cluster: unshifted_bd.cluster,
             };
 
             for shader in &obj.shaders {
@@ -96,160 +128,103 @@ pub trait ScreenBuffer: ScreenBufferCore {
 
             let ci = CharacterInfo {
                 chr: rd.chr,
+                attrs: rd.attrs,
+                // shaders could have replaced the char
+                cluster: rd.cluster.filter(|c| c.base() == rd.chr.chr),
+                alpha: obj.alpha,
                 layer: obj.layer,
                 screen_layer,
                 display_id: obj_id,
             };
-            let idx = self.idx_of_unchecked(rd.pos);
-            self.cell_info_mut()[idx].info.insert(obj_id, ci);
-
-            touched.insert(idx);
-        }
-
-        let (buf_cols, buf_rows) = self.size();
-        for (&row_u16, ivs) in &update_intervals {
-            if row_u16 >= buf_rows {
-                continue;
-            }
-            let row = row_u16 as i32;
-            for iv in ivs {
-                let (start_x, end_x) = iv.interval;
-
-                if end_x <= start_x {
-                    continue;
-                }
-
-                let end_x = end_x.min(buf_cols as usize);
-
-                for x in start_x..end_x {
-                    let x_i32 = x as i32;
-                    if x_i32 < 0 {
-                        continue;
-                    }
-                    let pos = Point { x: x_i32, y: row };
-
-                    // skip if out of total bounds
-                    if pos.x as u16 >= buf_cols || pos.y as u16 >= buf_rows {
-                        continue;
-                    }
-
-                    let idx = self.idx_of_unchecked(pos);
-
-                    if !touched.contains(&idx) {
-                        self.cell_info_mut()[idx].info.remove(&obj_id);
-                    }
-                }
-            }
+            placed.push((self.idx_of_unchecked(rd.pos), ci));
         }
 
+        // cells, the object does not cover anymore, have to show what is below it again
+        let vacated = self.cell_info_mut().place(obj_id, placed);
+        self.intervals_mut().merge_cell_ranges(vacated);
         Ok(())
     }
-    /// Remove an object from the buffer.
-    fn remove_from_buffer(
-        &mut self,
-        obj: &DrawObject,
-        obj_id: ObjectId,
-        sprites: &SpriteRegistry,
-        bounds: &Rect<i32>,
-    ) {
-        let drawable = &*obj.drawable;
-        let opt_c = drawable.bounding_iv(sprites);
-
-        let ivs: HashMap<u16, Vec<UpdateInterval>> =
-            self.handle_none_interval_creator(opt_c, bounds.p1);
-        let (buf_cols, buf_rows) = self.size();
 
-        for (row, iv_list) in ivs.into_iter() {
-            if row >= buf_rows {
-                continue;
-            }
-
-            for iv in iv_list {
-                let (start, end) = iv.interval;
-                let clamped_end = end.min(buf_cols as usize);
-                if start >= clamped_end {
-                    continue;
-                }
-
-                for x in start..clamped_end {
-                    let pos = Point {
-                        x: x as i32,
-                        y: row as i32,
-                    };
-                    let idx = self.idx_of_unchecked(pos);
-                    self.cell_info_mut()[idx].info.remove(&obj_id);
-                }
-            }
-        }
+    /// Remove an object from every cell it has been drawn to.
+    /// Its drawable is not asked, so this also works for objects, whose drawable can not be trusted anymore
+    fn remove_from_buffer(&mut self, obj_id: ObjectId) {
+        let covered = self.cell_info_mut().remove(obj_id);
+        self.intervals_mut().merge_cell_ranges(covered);
     }
 
+    /// Send every cell inside the dirty intervals, whose composed char differs from
+    /// what has last been sent, to the drawer and flush it afterwards.
+    ///
+    /// Runs of unchanged cells split the batches, so they are skipped entirely.
+    /// Forced intervals are always redrawn.
+    /// Cells covered by the right half of a wide char are continuation cells and never sent on their own.
     fn update_terminal(&mut self, expand: usize) -> Result<(), DrawError> {
         self.intervals_mut().expand_regions(expand);
         self.intervals_mut().merge_intervals();
         let intervals = self.intervals_mut().dump_intervals();
         let (cols, rows) = self.size();
+        let max_idx = cols as usize * rows as usize;
 
+        let mut batches: Vec<BatchDrawInfo> = Vec::new();
         for iv in intervals {
             let (start, end) = iv.interval;
-            let max_idx = cols as usize * rows as usize;
             let start = start.min(max_idx);
             let end = end.min(max_idx);
-
-            let cells = self.cell_info_mut();
-            let mut batch = BatchDrawInfo {
-                start_x: 0,
-                y: 0,
-                segments: Vec::new(),
+            let forced = iv.iv_type == UpdateIntervalType::Forced;
+
+            let mut current_batch: Option<BatchDrawInfo> = None;
+            // cells before this index are covered by a wide char
+            let mut covered_until = if start < end && self.is_continuation(start) {
+                start + 1
+            } else {
+                start
             };
-            let mut current_segment: Option<BatchSegment> = None;
-
-            let slice = &cells[start..end];
-            for (i, cell) in slice.iter().enumerate() {
-                let idx = start + i;
-                let y = (idx / cols as usize) as u16;
-                let x = (idx % cols as usize) as u16;
-
-                let chr_to_write = if let Some((_, char)) = Self::get_char_to_write(cell) {
-                    char
-                } else {
-                    TerminalChar {
-                        chr: ' ',
-                        fg_color: None,
-                        bg_color: None,
-                    }
-                };
-
-                let colors = (chr_to_write.fg_color, chr_to_write.bg_color);
-
-                if current_segment.is_none() {
-                    current_segment = Some(BatchSegment {
-                        text: chr_to_write.chr.to_string(),
-                        fg_color: colors.0,
-                        bg_color: colors.1,
-                    });
-                    batch.start_x = x;
-                    batch.y = y;
-                } else if let Some(seg) = &mut current_segment {
-                    if seg.fg_color == colors.0 && seg.bg_color == colors.1 {
-                        seg.text.push(chr_to_write.chr);
-                    } else {
-                        batch.segments.push(seg.clone());
-                        *seg = BatchSegment {
-                            text: chr_to_write.chr.to_string(),
-                            fg_color: colors.0,
-                            bg_color: colors.1,
-                        };
+            for idx in start..end {
+                if idx % cols as usize == 0 {
+                    covered_until = idx;
+                }
+                if idx < covered_until {
+                    // the terminal cursor already moved past this cell,
+                    // so a running batch simply continues after it
+                    self.front_buffer_mut()[idx] = None;
+                    continue;
+                }
+                let chr_to_write = self.display_char(idx);
+                let width = chr_to_write.width();
+                covered_until = idx + width;
+
+                let emitted = &mut self.front_buffer_mut()[idx];
+                if !forced && *emitted == Some(chr_to_write) {
+                    if let Some(batch) = current_batch.take() {
+                        batches.push(batch);
                     }
+                    continue;
+                }
+                *emitted = Some(chr_to_write);
+                if width > 1 && idx + 1 < max_idx {
+                    // the right half gets overwritten, even if it lies outside of the interval
+                    self.front_buffer_mut()[idx + 1] = None;
                 }
+
+                current_batch
+                    .get_or_insert_with(|| BatchDrawInfo {
+                        start_x: (idx % cols as usize) as u16,
+                        y: (idx / cols as usize) as u16,
+                        segments: Vec::new(),
+                    })
+                    .push_char(&chr_to_write);
             }
 
-            if let Some(seg) = current_segment {
-                batch.segments.push(seg);
+            if let Some(batch) = current_batch {
+                batches.push(batch);
             }
+        }
 
+        let size = self.size();
+        for batch in batches {
             if let Err(e) = self
                 .drawer_sender()
-                .send(CellDrawerCommand::SetString(batch, self.size()))
+                .send(CellDrawerCommand::SetString(batch, size))
             {
                 log::error!("Failed to send SetString to drawer thread: {}", e);
             }
@@ -264,15 +239,59 @@ pub trait ScreenBuffer: ScreenBufferCore {
     fn mark_all_dirty(&mut self, new_size: (u16, u16)) {
         self.invalidate_entire_screen();
         let capacity = new_size.0 as usize * new_size.1 as usize;
-        if self.cell_info_mut().len() != capacity {
-            self.cell_info_mut()
-                .resize_with(capacity, || CharacterInfoList {
-                    info: HashMap::new(),
-                });
+        if self.cell_info().len() != capacity {
+            self.cell_info_mut().reset(capacity);
         }
+        // the terminal content is unknown after a resize
+        let front_buffer = self.front_buffer_mut();
+        front_buffer.clear();
+        front_buffer.resize(capacity, None);
     }
     fn drawer_sender(&self) -> std::sync::mpsc::SyncSender<CellDrawerCommand>;
 
+    /// Resize the buffer in place, dropping all of its content.
+    /// The drawer is kept alive
+    fn resize(&mut self, new_size: (u16, u16));
+
+    /// Handle to the frames captured by the drawer, if it is headless
+    fn capture(&self) -> Option<FrameCapture>;
+
+    /// Errors of the drawer thread since the last call
+    fn take_drawer_errors(&self) -> Vec<DrawError> {
+        Vec::new()
+    }
+
+    /// Hand the cursor state to the drawer
+    fn set_cursor(&self, cursor: CursorState) {
+        if let Err(e) = self
+            .drawer_sender()
+            .send(CellDrawerCommand::SetCursor(cursor))
+        {
+            log::error!("Failed to send SetCursor to drawer thread: {}", e);
+        }
+    }
+
+    /// Hand lines to the drawer, that get printed above the drawn content
+    fn print_above(&self, text: String) {
+        if let Err(e) = self
+            .drawer_sender()
+            .send(CellDrawerCommand::PrintAbove(text))
+        {
+            log::error!("Failed to send PrintAbove to drawer thread: {}", e);
+        }
+    }
+
+    /// Block until the drawer has handled every command sent before
+    fn wait_for_drawer(&self) -> Result<(), DrawError> {
+        let (tx, rx) = mpsc::channel();
+        self.drawer_sender()
+            .send(CellDrawerCommand::Barrier(tx))
+            .map_err(|e| DrawError::ChannelSendError(e.to_string()))?;
+        rx.recv()
+            .map_err(|e| DrawError::ChannelSendError(e.to_string()))?;
+        Ok(())
+    }
+
     fn idx_of_unchecked(&self, pos: Point<i32>) -> usize {
         (pos.y as usize) * self.size().0 as usize + pos.x as usize
     }
@@ -287,14 +306,85 @@ pub trait ScreenBuffer: ScreenBufferCore {
         }
     }
 
-    fn get_char_to_write(cell: &CharacterInfoList) -> Option<(ObjectId, TerminalChar)> {
-        cell.info
-            .iter()
-            .max_by_key(|(_, c)| (c.screen_layer, c.layer))
-            .map(|(obj_id, c)| (*obj_id, c.chr))
+    /// The char, that is currently on top of every cell,
+    /// independent of what has already been flushed to the drawer.
+    /// Continuation cells of wide chars are left empty
+    fn composed_content(&self) -> TerminalContentInformation {
+        let cols = (self.size().0 as usize).max(1);
+        let len = self.cell_info().len();
+        let mut content = Vec::with_capacity(len);
+        let mut attributes = Vec::with_capacity(len);
+        let mut clusters = Vec::with_capacity(len);
+        let mut covered_until = 0;
+        for idx in 0..len {
+            if idx % cols == 0 {
+                covered_until = idx;
+            }
+            let shown = if idx < covered_until {
+                None
+            } else {
+                self.shown_char(idx)
+            };
+            if let Some(chr) = shown {
+                covered_until = idx + chr.width();
+            }
+            content.push(shown.map(|c| c.chr));
+            attributes.push(shown.map(|c| c.attrs).unwrap_or_default());
+            clusters.push(shown.and_then(|c| c.cluster));
+        }
+        TerminalContentInformation {
+            size: self.size(),
+            content,
+            attributes,
+            clusters,
+            cursor: CursorState::default(),
+            amount_of_changed_chars: 0,
+            amount_of_draw_commands: 0,
+        }
+    }
+
+    /// The char, that gets drawn at the index, with blanks for empty cells
+    fn display_char(&self, idx: usize) -> StyledChar {
+        self.shown_char(idx)
+            .unwrap_or(StyledChar::from(TerminalChar {
+                chr: ' ',
+                fg_color: None,
+                bg_color: None,
+            }))
+    }
+
+    /// The char, that gets drawn at the index, None for empty cells.
+    /// Wide chars in the last column are replaced by a blank, since they don't fit,
+    /// which leaves the cell empty, unless the char has a background
+    fn shown_char(&self, idx: usize) -> Option<StyledChar> {
+        let (_, chr) = self.cell_info().top(idx)?;
+        let cols = self.size().0 as usize;
+        if chr.width() > 1 && idx % cols.max(1) + 1 >= cols {
+            return chr.chr.bg_color.map(|bg| {
+                StyledChar::from(TerminalChar {
+                    chr: ' ',
+                    fg_color: None,
+                    bg_color: Some(bg),
+                })
+            });
+        }
+        Some(chr)
     }
 
-    fn drop(&mut self);
+    /// Whether the cell is covered by the right half of a wide char
+    fn is_continuation(&self, idx: usize) -> bool {
+        let cols = (self.size().0 as usize).max(1);
+        let mut x = idx - idx % cols;
+        while x < idx {
+            x += self.display_char(x).width();
+        }
+        x > idx
+    }
+
+    /// The composed char of all objects in the cell, together with the highest visible object
+    fn get_char_to_write(cell: &CharacterInfoList) -> Option<(ObjectId, StyledChar)> {
+        compositing::composite(cell.layers())
+    }
 
     fn handle_none_interval_creator(
         &mut self,
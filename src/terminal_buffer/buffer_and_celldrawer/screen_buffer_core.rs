@@ -1,18 +1,26 @@
 use std::fmt::Debug;
 
 use crate::{
-    UpdateIntervalHandler, terminal_buffer::CharacterInfoList,
+    UpdateIntervalHandler,
+    terminal_buffer::{LayeredCells, StyledChar},
     update_interval_handler::UpdateIntervalCreator,
 };
 
 /// Trait that contains all internal state required by a screen buffer.
 /// contains some utility functions
 pub trait ScreenBufferCore: Sized + Debug {
-    /// Return a mutable reference to the per‑cell info list
-    fn cell_info_mut(&mut self) -> &mut Vec<CharacterInfoList>;
+    /// Return a mutable reference to the chars of every object in every cell
+    fn cell_info_mut(&mut self) -> &mut LayeredCells;
 
-    /// Return reference to the per‑cell info list
-    fn cell_info(&self) -> &Vec<CharacterInfoList>;
+    /// Return reference to the chars of every object in every cell
+    fn cell_info(&self) -> &LayeredCells;
+
+    /// Return a mutable reference to the chars, that have last been sent to the drawer.
+    /// `None` means, that the content of that cell on the terminal is unknown
+    fn front_buffer_mut(&mut self) -> &mut Vec<Option<StyledChar>>;
+
+    /// Return reference to the chars, that have last been sent to the drawer
+    fn front_buffer(&self) -> &Vec<Option<StyledChar>>;
 
     /// Return a mutable reference to the interval handler
     fn intervals_mut(&mut self) -> &mut UpdateIntervalHandler;
@@ -3,7 +3,7 @@ use common_stdx::Point;
 use dyn_clone::DynClone;
 use std::fmt::Debug;
 
-use crate::BasicDraw;
+use crate::{BasicDraw, CellAttributes};
 
 pub trait Shader: Send + Sync + Debug + DynClone {
     fn apply(&self, draw: &mut BasicDraw, frame_size: (usize, usize), top_left: Point<i32>);
@@ -219,3 +219,33 @@ impl Shader for SwapColors {
         std::mem::swap(&mut draw.chr.fg_color, &mut draw.chr.bg_color);
     }
 }
+
+/// Enable the attributes on every char
+#[derive(Debug, Clone, Copy)]
+pub struct AddAttributes(pub CellAttributes);
+
+impl Shader for AddAttributes {
+    fn apply(&self, draw: &mut BasicDraw, _frame_size: (usize, usize), _top_left: Point<i32>) {
+        draw.attrs.insert(self.0);
+    }
+}
+
+/// Disable the attributes on every char
+#[derive(Debug, Clone, Copy)]
+pub struct RemoveAttributes(pub CellAttributes);
+
+impl Shader for RemoveAttributes {
+    fn apply(&self, draw: &mut BasicDraw, _frame_size: (usize, usize), _top_left: Point<i32>) {
+        draw.attrs.remove(self.0);
+    }
+}
+
+/// Flip the attributes on every char
+#[derive(Debug, Clone, Copy)]
+pub struct ToggleAttributes(pub CellAttributes);
+
+impl Shader for ToggleAttributes {
+    fn apply(&self, draw: &mut BasicDraw, _frame_size: (usize, usize), _top_left: Point<i32>) {
+        draw.attrs.toggle(self.0);
+    }
+}
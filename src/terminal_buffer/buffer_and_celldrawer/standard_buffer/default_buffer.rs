@@ -1,32 +1,50 @@
-use std::{collections::HashMap, marker::PhantomData, thread::JoinHandle};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex, PoisonError},
+    thread::JoinHandle,
+};
 
 use crate::{
-    ScreenBuffer, UpdateIntervalHandler,
+    DrawError, ScreenBuffer, UpdateIntervalHandler,
     terminal_buffer::{
-        CellDrawer, CharacterInfoList, ScreenBufferCore,
-        buffer_and_celldrawer::screen_buffer::CellDrawerCommand,
+        CellDrawer, LayeredCells, ScreenBufferCore, StyledChar,
+        buffer_and_celldrawer::{
+            screen_buffer::CellDrawerCommand, standard_celldrawer::test_celldrawer::FrameCapture,
+        },
     },
 };
 
+/// flush errors kept, while nobody takes them
+const MAX_DRAWER_ERRORS: usize = 64;
+
 #[derive(Debug)]
 pub struct DefaultScreenBuffer<CD: CellDrawer + Send + 'static> {
-    cells: Vec<CharacterInfoList>,
+    cells: LayeredCells,
+    front_buffer: Vec<Option<StyledChar>>,
     intervals: UpdateIntervalHandler,
     size: (u16, u16),
     drawer_tx: std::sync::mpsc::SyncSender<CellDrawerCommand>,
-
-    /// todo: implement joining
+    capture: Option<FrameCapture>,
+    /// flush errors of the drawer thread, until the renderer takes them
+    drawer_errors: Arc<Mutex<Vec<DrawError>>>,
+    /// joined on drop, after the drawer has flushed
     drawer_handle: Option<JoinHandle<()>>,
     _phantom: std::marker::PhantomData<CD>,
 }
 
 impl<CD: CellDrawer + Send + 'static> ScreenBufferCore for DefaultScreenBuffer<CD> {
-    fn cell_info_mut(&mut self) -> &mut Vec<CharacterInfoList> {
+    fn cell_info_mut(&mut self) -> &mut LayeredCells {
         &mut self.cells
     }
-    fn cell_info(&self) -> &Vec<CharacterInfoList> {
+    fn cell_info(&self) -> &LayeredCells {
         &self.cells
     }
+    fn front_buffer_mut(&mut self) -> &mut Vec<Option<StyledChar>> {
+        &mut self.front_buffer
+    }
+    fn front_buffer(&self) -> &Vec<Option<StyledChar>> {
+        &self.front_buffer
+    }
 
     fn intervals_mut(&mut self) -> &mut UpdateIntervalHandler {
         &mut self.intervals
@@ -37,12 +55,10 @@ impl<CD: CellDrawer + Send + 'static> ScreenBufferCore for DefaultScreenBuffer<C
     }
 }
 
-impl<CD> ScreenBuffer for DefaultScreenBuffer<CD>
-where
-    CD: CellDrawer + Send + 'static,
-{
-    type Drawer = CD;
-    fn new(size: (u16, u16)) -> Self {
+impl<CD: CellDrawer + Send + 'static> DefaultScreenBuffer<CD> {
+    /// Create a buffer around an already configured drawer,
+    /// for drawers that need more setup than CellDrawer::init provides
+    pub fn with_drawer(size: (u16, u16), mut drawer: CD) -> Self {
         let capacity = size.0 as usize * size.1 as usize;
 
         use std::sync::mpsc;
@@ -52,17 +68,30 @@ where
         // let users define
         let (drawer_tx, rx) = mpsc::sync_channel::<CellDrawerCommand>(10000);
 
+        let capture = drawer.capture();
+        let drawer_errors = Arc::new(Mutex::new(Vec::new()));
+        let errors = drawer_errors.clone();
+
         // spawn a thread that owns the receiver and the writer
         let drawer_handle = thread::spawn(move || {
-            let mut drawer = CD::init(rx);
-
             // process commands until the sender is dropped
-            while let Ok(cmd) = drawer.recv() {
+            while let Ok(cmd) = rx.recv() {
                 match cmd {
                     CellDrawerCommand::SetString(batch, size) => drawer.set_string(batch, size),
                     CellDrawerCommand::Flush => {
-                        let _ = drawer.flush();
+                        if let Err(e) = drawer.flush() {
+                            let mut errors = errors.lock().unwrap_or_else(PoisonError::into_inner);
+                            // a drawer failing every frame should not grow the list forever
+                            if errors.len() < MAX_DRAWER_ERRORS {
+                                errors.push(e);
+                            }
+                        }
+                    }
+                    CellDrawerCommand::Barrier(done) => {
+                        let _ = done.send(());
                     }
+                    CellDrawerCommand::SetCursor(cursor) => drawer.set_cursor(cursor),
+                    CellDrawerCommand::PrintAbove(text) => drawer.print_above(&text),
                     CellDrawerCommand::Stop => {
                         break;
                     }
@@ -71,27 +100,63 @@ where
         });
 
         DefaultScreenBuffer {
-            cells: vec![
-                CharacterInfoList {
-                    info: HashMap::new()
-                };
-                capacity
-            ],
+            cells: LayeredCells::new(capacity),
+            front_buffer: vec![None; capacity],
             intervals: UpdateIntervalHandler::new(size.0, size.1),
             size,
             drawer_tx,
+            capture,
+            drawer_errors,
             drawer_handle: Some(drawer_handle),
             _phantom: PhantomData,
         }
     }
+}
+
+impl<CD> ScreenBuffer for DefaultScreenBuffer<CD>
+where
+    CD: CellDrawer + Send + 'static,
+{
+    type Drawer = CD;
+    fn new(size: (u16, u16)) -> Self {
+        Self::with_drawer(size, CD::init())
+    }
 
     fn drawer_sender(&self) -> std::sync::mpsc::SyncSender<CellDrawerCommand> {
         self.drawer_tx.clone()
     }
+    fn resize(&mut self, new_size: (u16, u16)) {
+        let capacity = new_size.0 as usize * new_size.1 as usize;
+        self.cells.reset(capacity);
+        self.front_buffer.clear();
+        self.front_buffer.resize(capacity, None);
+        self.intervals = UpdateIntervalHandler::new(new_size.0, new_size.1);
+        self.size = new_size;
+    }
+
+    fn capture(&self) -> Option<FrameCapture> {
+        self.capture.clone()
+    }
+
+    fn take_drawer_errors(&self) -> Vec<DrawError> {
+        std::mem::take(
+            &mut *self
+                .drawer_errors
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+}
+
+impl<CD: CellDrawer + Send + 'static> Drop for DefaultScreenBuffer<CD> {
+    /// flushes the drawer and waits for its thread to end
     fn drop(&mut self) {
+        let _ = self.drawer_tx.send(CellDrawerCommand::Flush);
         let _ = self.drawer_tx.send(CellDrawerCommand::Stop);
-        if let Some(handle) = self.drawer_handle.take() {
-            let _ = handle.join();
+        if let Some(handle) = self.drawer_handle.take()
+            && handle.join().is_err()
+        {
+            log::error!("drawer thread panicked");
         }
     }
 }
@@ -0,0 +1,222 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Stdout, Write, stdout};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    DrawError, SharedClock,
+    terminal_buffer::{
+        CellDrawer,
+        buffer_and_celldrawer::{
+            BatchDrawInfo,
+            standard_celldrawer::{
+                color_mode::ColorMode,
+                crossterm_celldrawer::{CrosstermOptions, encode_batch},
+            },
+        },
+    },
+};
+
+/// Drawer, that records the output for the terminal into an asciicast v2 file,
+/// which can be played back with asciinema.
+///
+/// Every flush becomes one output event, timestamped relative to the first frame.
+/// The drawer needs to know where to record to, so create it with new or create
+/// and pass it to DefaultScreenBuffer::with_drawer
+pub struct AsciicastCellDrawer {
+    /// None for a drawer created through CellDrawer::init, which has nowhere to record to
+    out: Option<BufWriter<Box<dyn Write + Send>>>,
+    /// should match the options of the terminal drawer, so the recording shows the same output
+    options: CrosstermOptions,
+    tee: Option<BufWriter<Stdout>>,
+    clock: SharedClock,
+    /// escape sequences written since the last flush
+    pending: String,
+    /// size of the recorded terminal, known after the first frame
+    size: Option<(u16, u16)>,
+    resized: bool,
+    start: Option<Instant>,
+}
+
+impl fmt::Debug for AsciicastCellDrawer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsciicastCellDrawer")
+            .field("recording", &self.out.is_some())
+            .field("options", &self.options)
+            .field("tee", &self.tee.is_some())
+            .field("size", &self.size)
+            .field("start", &self.start)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsciicastCellDrawer {
+    /// record into any writer, encoding like CrosstermCellDrawer::init does
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self::with_output(Some(BufWriter::new(Box::new(writer))))
+    }
+
+    fn with_output(out: Option<BufWriter<Box<dyn Write + Send>>>) -> Self {
+        AsciicastCellDrawer {
+            out,
+            options: CrosstermOptions::detect(),
+            tee: None,
+            clock: SharedClock::default(),
+            pending: String::new(),
+            size: None,
+            resized: false,
+            start: None,
+        }
+    }
+
+    /// record into a newly created file at the path
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(File::create(path)?))
+    }
+
+    /// encode with the options of the drawer, that draws onto the real terminal
+    pub fn options(mut self, options: CrosstermOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// also draw everything onto the terminal, while recording
+    pub fn tee_stdout(mut self, tee: bool) -> Self {
+        self.tee = tee.then(|| BufWriter::new(stdout()));
+        self
+    }
+
+    /// clock used for the event timestamps
+    pub fn clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn out(&mut self) -> io::Result<&mut BufWriter<Box<dyn Write + Send>>> {
+        self.out.as_mut().ok_or_else(missing_writer)
+    }
+
+    fn write_header(&mut self, size: (u16, u16)) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let env = term_env(self.options.color_mode);
+        writeln!(
+            self.out()?,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"env\": {{{}}}}}",
+            size.0,
+            size.1,
+            timestamp,
+            env
+        )
+    }
+
+    fn write_event(&mut self, time: f64, kind: &str, data: &str) -> io::Result<()> {
+        writeln!(
+            self.out()?,
+            "[{:.6}, \"{}\", \"{}\"]",
+            time,
+            kind,
+            escape_json(data)
+        )
+    }
+}
+
+impl CellDrawer for AsciicastCellDrawer {
+    /// records nothing, every flush reports the missing writer
+    fn init() -> Self {
+        Self::with_output(None)
+    }
+
+    fn set_string(&mut self, batch: BatchDrawInfo, size: (u16, u16)) {
+        match self.size {
+            None => {
+                if let Err(e) = self.write_header(size) {
+                    log::error!("Failed to write asciicast header: {}", e);
+                }
+                self.start = Some(self.clock.now());
+                self.size = Some(size);
+            }
+            Some(old) if old != size => {
+                self.size = Some(size);
+                self.resized = true;
+            }
+            _ => {}
+        }
+        let output = encode_batch(&batch, &self.options);
+        if let Some(tee) = &mut self.tee
+            && let Err(e) = tee.write_all(output.as_bytes())
+        {
+            log::error!("Failed to write to terminal BufWriter: {}", e);
+        }
+        self.pending.push_str(&output);
+    }
+
+    fn flush(&mut self) -> Result<(), DrawError> {
+        if let Some(tee) = &mut self.tee {
+            tee.flush()?;
+        }
+        if self.out.is_none() {
+            self.pending.clear();
+            return Err(missing_writer().into());
+        }
+        let Some(start) = self.start else {
+            // nothing has been drawn yet
+            return Ok(());
+        };
+        let time = self
+            .clock
+            .now()
+            .saturating_duration_since(start)
+            .as_secs_f64();
+
+        if self.resized
+            && let Some((w, h)) = self.size
+        {
+            self.write_event(time, "r", &format!("{}x{}", w, h))?;
+            self.resized = false;
+        }
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.write_event(time, "o", &pending)?;
+        }
+        self.out()?.flush()?;
+        Ok(())
+    }
+}
+
+fn missing_writer() -> io::Error {
+    io::Error::other(
+        "the asciicast drawer has nowhere to record to, create it with AsciicastCellDrawer::new or ::create",
+    )
+}
+
+/// the terminal a player should emulate, to show the colors the recording was encoded with
+fn term_env(color_mode: ColorMode) -> &'static str {
+    match color_mode {
+        ColorMode::TrueColor => "\"TERM\": \"xterm-256color\", \"COLORTERM\": \"truecolor\"",
+        ColorMode::Ansi256 => "\"TERM\": \"xterm-256color\"",
+        ColorMode::Ansi16 => "\"TERM\": \"xterm\"",
+        ColorMode::Monochrome => "\"TERM\": \"xterm-mono\"",
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 16);
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => {
+                out.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
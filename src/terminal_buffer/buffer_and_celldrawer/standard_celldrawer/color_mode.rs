@@ -0,0 +1,167 @@
+use std::fmt::Write;
+
+use ascii_assets::Color;
+
+/// The amount of colors a terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// 24 bit colors
+    #[default]
+    TrueColor,
+    /// the xterm 256 color palette
+    Ansi256,
+    /// the 16 basic ansi colors
+    Ansi16,
+    /// no colors at all
+    Monochrome,
+}
+
+/// 4x4 bayer matrix, used for ordered dithering
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// levels of the 6x6x6 cube inside of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorMode {
+    /// Detect the color mode of the current terminal from the COLORTERM and TERM variables.
+    /// NO_COLOR disables colors entirely
+    pub fn detect() -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return ColorMode::Monochrome;
+        }
+        Self::from_env_values(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// Guess the color mode from the values of COLORTERM and TERM
+    pub fn from_env_values(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if let Some(ct) = colorterm {
+            let ct = ct.to_ascii_lowercase();
+            if ct == "truecolor" || ct == "24bit" {
+                return ColorMode::TrueColor;
+            }
+        }
+        let Some(term) = term.map(str::to_ascii_lowercase) else {
+            // most terminals without TERM, like the windows terminal, support true color
+            return ColorMode::TrueColor;
+        };
+        if term == "dumb" {
+            ColorMode::Monochrome
+        } else if term.contains("truecolor") || term.contains("24bit") || term.contains("direct") {
+            ColorMode::TrueColor
+        } else if term.contains("256") {
+            ColorMode::Ansi256
+        } else {
+            // the linux console, screen, plain xterm and friends
+            ColorMode::Ansi16
+        }
+    }
+
+    /// The sgr parameters selecting the color, like `38;2;255;0;0`.
+    /// Returns None, if the color should be left at the terminal default.
+    ///
+    /// `dither_pos` is the position of the cell, which enables ordered dithering for palette modes
+    pub fn sgr_params(
+        &self,
+        color: Option<Color>,
+        background: bool,
+        dither_pos: Option<(u16, u16)>,
+    ) -> Option<String> {
+        let color = color.filter(|c| !c.reset)?;
+        let (r, g, b) = match dither_pos {
+            Some(pos) => self.dither(color.rgb, pos),
+            None => color.rgb,
+        };
+        let mut out = String::new();
+        match self {
+            ColorMode::TrueColor => {
+                let _ = write!(
+                    out,
+                    "{};2;{};{};{}",
+                    if background { 48 } else { 38 },
+                    r,
+                    g,
+                    b
+                );
+            }
+            ColorMode::Ansi256 => {
+                let _ = write!(
+                    out,
+                    "{};5;{}",
+                    if background { 48 } else { 38 },
+                    nearest_ansi256((r, g, b))
+                );
+            }
+            ColorMode::Ansi16 => {
+                let idx = nearest_ansi16((r, g, b));
+                let base = match (background, idx < 8) {
+                    (false, true) => 30,
+                    (false, false) => 90 - 8,
+                    (true, true) => 40,
+                    (true, false) => 100 - 8,
+                };
+                let _ = write!(out, "{}", base + idx as u16);
+            }
+            ColorMode::Monochrome => return None,
+        }
+        Some(out)
+    }
+
+    /// spread the rgb value by the bayer threshold at the position,
+    /// so neighbouring cells round to different palette colors
+    fn dither(&self, (r, g, b): (u8, u8, u8), (x, y): (u16, u16)) -> (u8, u8, u8) {
+        let spread = match self {
+            ColorMode::Ansi256 => 40.0,
+            ColorMode::Ansi16 => 128.0,
+            ColorMode::TrueColor | ColorMode::Monochrome => return (r, g, b),
+        };
+        let threshold = (BAYER[y as usize % 4][x as usize % 4] as f32 + 0.5) / 16.0 - 0.5;
+        let offset = |v: u8| (v as f32 + threshold * spread).round().clamp(0.0, 255.0) as u8;
+        (offset(r), offset(g), offset(b))
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// index of the nearest color inside of the 256 color palette, ignoring the first 16 colors,
+/// since terminals tend to theme them
+pub fn nearest_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |v: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, l)| (v as i32 - **l as i32).abs())
+            .map(|(i, _)| i as u8)
+            .unwrap_or_default()
+    };
+    let (r6, g6, b6) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube_idx = 16 + 36 * r6 + 6 * g6 + b6;
+    let cube_rgb = (
+        CUBE_LEVELS[r6 as usize],
+        CUBE_LEVELS[g6 as usize],
+        CUBE_LEVELS[b6 as usize],
+    );
+
+    let avg = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray_step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + gray_step * 10;
+    let gray_idx = 232 + gray_step;
+
+    if distance(rgb, (gray, gray, gray)) < distance(rgb, cube_rgb) {
+        gray_idx
+    } else {
+        cube_idx
+    }
+}
+
+/// index of the nearest of the 16 basic ansi colors
+pub fn nearest_ansi16(rgb: (u8, u8, u8)) -> u8 {
+    (0..16u8)
+        .min_by_key(|i| distance(rgb, Color::ansi256_to_rgb(*i)))
+        .unwrap_or_default()
+}
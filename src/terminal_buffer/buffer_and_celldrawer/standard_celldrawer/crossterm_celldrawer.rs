@@ -1,86 +1,362 @@
-use std::{
-    io::{BufWriter, Stdout, Write, stdout},
-    sync::mpsc::Receiver,
-};
+use std::fmt;
+use std::io::{BufWriter, Write, stdout};
 
 use crate::{
-    DrawError,
+    DrawError, InlineRegion,
     terminal_buffer::{
-        CellDrawer,
-        buffer_and_celldrawer::{BatchDrawInfo, screen_buffer::CellDrawerCommand},
+        CellAttributes, CellDrawer, CursorState,
+        buffer_and_celldrawer::{BatchDrawInfo, standard_celldrawer::color_mode::ColorMode},
+        grapheme::graphemes,
     },
 };
 use ascii_assets;
 use crossterm::style::Color;
 use std::fmt::Write as FmtWrite;
 
-#[derive(Debug)]
+/// starts a synchronized update (DEC private mode 2026), the terminal holds back drawing until it ends
+pub const BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026h";
+/// ends a synchronized update and presents everything written since its start at once
+pub const END_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026l";
+
+/// Settings for how the CrosstermCellDrawer encodes its output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrosstermOptions {
+    pub color_mode: ColorMode,
+    /// use ordered dithering when mapping colors to a palette,
+    /// which looks better on gradients and sprites, but worse on flat colors
+    pub dither: bool,
+    /// wrap every flush in a synchronized update, so the terminal never shows half drawn frames.
+    /// Terminals without support simply ignore the sequences
+    pub synchronized_output: bool,
+    /// draw into a region of the normal screen, instead of the whole alternate screen.
+    /// Rows are relative to the top of the region
+    pub inline: Option<InlineRegion>,
+}
+
+/// true color and synchronized output, like detect chooses for most terminals
+impl Default for CrosstermOptions {
+    fn default() -> Self {
+        CrosstermOptions {
+            color_mode: ColorMode::default(),
+            dither: false,
+            synchronized_output: true,
+            inline: None,
+        }
+    }
+}
+
+impl CrosstermOptions {
+    /// options fitting the current terminal
+    pub fn detect() -> Self {
+        CrosstermOptions {
+            color_mode: ColorMode::detect(),
+            dither: false,
+            synchronized_output: std::env::var("TERM").map_or(true, |term| term != "dumb"),
+            inline: None,
+        }
+    }
+
+    pub fn color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    pub fn dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    pub fn synchronized_output(mut self, synchronized_output: bool) -> Self {
+        self.synchronized_output = synchronized_output;
+        self
+    }
+
+    pub fn inline(mut self, region: InlineRegion) -> Self {
+        self.inline = Some(region);
+        self
+    }
+
+    /// row of the terminal, that row 0 gets drawn onto
+    pub fn row_offset(&self) -> u16 {
+        self.inline.map_or(0, |region| region.origin)
+    }
+}
+
+/// Drawer, that writes escape sequences for a terminal.
+///
+/// Draws onto stdout by default, but can target any writer, like a pty, a socket or a file.
+/// Pass such a drawer to DefaultScreenBuffer::with_drawer
 pub struct CrosstermCellDrawer {
-    rx: Receiver<CellDrawerCommand>,
-    out: BufWriter<Stdout>,
+    out: BufWriter<Box<dyn Write + Send>>,
+    options: CrosstermOptions,
+    /// whether anything has been drawn since the last flush
+    drawn: bool,
+    cursor: CursorState,
 }
 
-impl CellDrawer for CrosstermCellDrawer {
-    fn init(rx: Receiver<CellDrawerCommand>) -> Self {
+impl fmt::Debug for CrosstermCellDrawer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrosstermCellDrawer")
+            .field("options", &self.options)
+            .field("drawn", &self.drawn)
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CrosstermCellDrawer {
+    /// draw onto any writer with the default options.
+    /// The options of the local terminal are not detected, since the writer may lead somewhere else
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self::with_writer(writer, CrosstermOptions::default())
+    }
+
+    /// draw onto any writer with explicit options
+    pub fn with_writer<W: Write + Send + 'static>(writer: W, options: CrosstermOptions) -> Self {
         CrosstermCellDrawer {
-            out: BufWriter::new(stdout()),
-            rx,
+            out: BufWriter::new(Box::new(writer)),
+            options,
+            drawn: false,
+            cursor: CursorState::default(),
         }
     }
 
-    fn recv(&self) -> Result<CellDrawerCommand, std::sync::mpsc::RecvError> {
-        self.rx.recv()
+    /// draw onto stdout with explicit options,
+    /// to be passed to DefaultScreenBuffer::with_drawer
+    pub fn with_options(options: CrosstermOptions) -> Self {
+        Self::with_writer(stdout(), options)
     }
 
-    fn set_string(&mut self, batch: BatchDrawInfo, _size: (u16, u16)) {
-        let text_len: usize = batch.segments.iter().map(|s| s.text.len()).sum();
-        let mut output = String::with_capacity(text_len + 256);
-
-        let _ = write!(output, "\x1b[{};{}H", batch.y + 1, batch.start_x + 1);
+    pub fn options(&self) -> CrosstermOptions {
+        self.options
+    }
 
-        let mut current_fg: Option<Color> = None;
-        let mut current_bg: Option<Color> = None;
+    /// the cursor sequence, moved into the inline region
+    fn cursor_sequence(&self) -> String {
+        let mut cursor = self.cursor;
+        cursor.position.1 += self.options.row_offset();
+        cursor.escape_sequence()
+    }
 
-        for seg in &batch.segments {
-            let desired_fg = to_crossterm_color(seg.fg_color);
-            let desired_bg = to_crossterm_color(seg.bg_color);
+    /// Write the lines into the rows right above the inline region.
+    /// Uses the free rows below the region first, by moving it down,
+    /// afterwards the rows above it get scrolled up into the scrollback
+    fn encode_print_above(&mut self, region: InlineRegion, lines: &[&str]) -> String {
+        let mut out = String::from("\x1b[0m");
+        let free = region
+            .terminal_rows
+            .saturating_sub(region.origin + region.height);
+        let (inserted, scrolled) = lines.split_at(lines.len().min(free as usize));
 
-            if current_fg.as_ref() != Some(&desired_fg) {
-                match desired_fg {
-                    Color::Reset => output.push_str("\x1b[39m"),
-                    Color::Rgb { r, g, b } => {
-                        let _ = write!(output, "\x1b[38;2;{};{};{}m", r, g, b);
-                    }
-                    _ => {}
-                }
-                current_fg = Some(desired_fg);
+        let mut origin = region.origin;
+        if !inserted.is_empty() {
+            // insert blank lines at the top of the region, which pushes it down
+            let _ = write!(out, "\x1b[{};1H\x1b[{}L", origin + 1, inserted.len());
+            for line in inserted {
+                let _ = write!(out, "{}\r\n", line);
             }
-
-            if current_bg.as_ref() != Some(&desired_bg) {
-                match desired_bg {
-                    Color::Reset => output.push_str("\x1b[49m"),
-                    Color::Rgb { r, g, b } => {
-                        let _ = write!(output, "\x1b[48;2;{};{};{}m", r, g, b);
-                    }
-                    _ => {}
+            origin += inserted.len() as u16;
+        }
+        if !scrolled.is_empty() {
+            if origin == 0 {
+                log::warn!("no rows left above the inline region, dropping printed lines");
+            } else {
+                // limit scrolling to the rows above the region
+                let _ = write!(out, "\x1b[1;{}r\x1b[{};1H", origin, origin);
+                for line in scrolled {
+                    let _ = write!(out, "\r\n\x1b[2K{}", line);
                 }
-                current_bg = Some(desired_bg);
+                out.push_str("\x1b[r");
             }
+        }
+        self.options.inline = Some(InlineRegion { origin, ..region });
+        out
+    }
+}
 
-            output.push_str(&seg.text);
+impl Drop for CrosstermCellDrawer {
+    /// leaves the last frame of an inline region in the scrollback,
+    /// with the cursor on the line below it
+    fn drop(&mut self) {
+        if let Some(region) = self.options.inline {
+            let _ = write!(
+                self.out,
+                "\x1b[0m\x1b[{};1H\r\n",
+                region.origin + region.height
+            );
+            let _ = self.out.flush();
         }
+    }
+}
 
-        output.push_str("\x1b[0m");
+impl CellDrawer for CrosstermCellDrawer {
+    /// detects the color mode of the terminal
+    fn init() -> Self {
+        Self::with_options(CrosstermOptions::detect())
+    }
 
+    fn set_string(&mut self, batch: BatchDrawInfo, _size: (u16, u16)) {
+        let mut output = encode_batch(&batch, &self.options);
+        if !self.drawn {
+            if self.cursor.visible {
+                // keep the cursor from jumping around while drawing
+                output.insert_str(0, "\x1b[?25l");
+            }
+            if self.options.synchronized_output {
+                output.insert_str(0, BEGIN_SYNCHRONIZED_UPDATE);
+            }
+            self.drawn = true;
+        }
         if let Err(e) = self.out.write_all(output.as_bytes()) {
             log::error!("Failed to write to terminal BufWriter: {}", e);
         }
     }
 
+    /// puts the cursor back into place and ends the synchronized update, if anything has been drawn
     fn flush(&mut self) -> Result<(), DrawError> {
+        if self.drawn {
+            self.drawn = false;
+            self.out.write_all(self.cursor_sequence().as_bytes())?;
+            if self.options.synchronized_output {
+                self.out.write_all(END_SYNCHRONIZED_UPDATE.as_bytes())?;
+            }
+        }
         self.out.flush()?;
         Ok(())
     }
+
+    fn set_cursor(&mut self, cursor: CursorState) {
+        self.cursor = cursor;
+        if self.drawn {
+            // gets applied by the next flush
+            return;
+        }
+        let output = self.cursor_sequence();
+        if let Err(e) = self
+            .out
+            .write_all(output.as_bytes())
+            .and_then(|_| self.out.flush())
+        {
+            log::error!("Failed to write cursor state: {}", e);
+        }
+    }
+
+    fn print_above(&mut self, text: &str) {
+        let Some(region) = self.options.inline else {
+            log::warn!("printing above the drawn content needs an inline region");
+            return;
+        };
+        let lines: Vec<&str> = text.lines().collect();
+        let mut output = self.encode_print_above(region, &lines);
+        // while drawing, the open synchronized update gets ended by the next flush
+        if !self.drawn {
+            output.push_str(&self.cursor_sequence());
+            if self.options.synchronized_output {
+                output.insert_str(0, BEGIN_SYNCHRONIZED_UPDATE);
+                output.push_str(END_SYNCHRONIZED_UPDATE);
+            }
+        }
+        if let Err(e) = self
+            .out
+            .write_all(output.as_bytes())
+            .and_then(|_| self.out.flush())
+        {
+            log::error!("Failed to print above the inline region: {}", e);
+        }
+    }
+}
+
+/// Encode a batch into the escape sequences, that draw it onto a terminal
+pub fn encode_batch(batch: &BatchDrawInfo, options: &CrosstermOptions) -> String {
+    let text_len: usize = batch.segments.iter().map(|s| s.text.len()).sum();
+    let mut output = String::with_capacity(text_len + 256);
+
+    let _ = write!(
+        output,
+        "\x1b[{};{}H",
+        batch.y + options.row_offset() + 1,
+        batch.start_x + 1
+    );
+
+    let mode = options.color_mode;
+    // dithering picks a color per cell, instead of per segment
+    let per_cell = options.dither && matches!(mode, ColorMode::Ansi256 | ColorMode::Ansi16);
+
+    let mut current_fg: Option<Option<String>> = None;
+    let mut current_bg: Option<Option<String>> = None;
+    // every batch ends with a reset, so it starts without attributes
+    let mut current_attrs = CellAttributes::NONE;
+    let mut x = batch.start_x;
+
+    for seg in &batch.segments {
+        let codes = seg.attrs.sgr_transition(current_attrs);
+        if !codes.is_empty() {
+            let codes: Vec<String> = codes.iter().map(u8::to_string).collect();
+            let _ = write!(output, "\x1b[{}m", codes.join(";"));
+            current_attrs = seg.attrs;
+        }
+        if !per_cell {
+            push_sgr(
+                &mut output,
+                &mut current_fg,
+                mode.sgr_params(seg.fg_color, false, None),
+                "39",
+            );
+            push_sgr(
+                &mut output,
+                &mut current_bg,
+                mode.sgr_params(seg.bg_color, true, None),
+                "49",
+            );
+            if seg.text.is_ascii() {
+                output.push_str(&seg.text);
+                x = x.saturating_add(seg.text.len() as u16);
+                continue;
+            }
+        }
+        for (_, cluster, width) in graphemes(&seg.text) {
+            if per_cell {
+                let pos = Some((x, batch.y));
+                push_sgr(
+                    &mut output,
+                    &mut current_fg,
+                    mode.sgr_params(seg.fg_color, false, pos),
+                    "39",
+                );
+                push_sgr(
+                    &mut output,
+                    &mut current_bg,
+                    mode.sgr_params(seg.bg_color, true, pos),
+                    "49",
+                );
+            }
+            output.push_str(cluster);
+            x = x.saturating_add(width as u16);
+            if width > 1 || cluster.chars().nth(1).is_some() {
+                // terminals disagree on the width of some clusters,
+                // so the cursor is moved to the next cell explicitly
+                let _ = write!(output, "\x1b[{}G", x + 1);
+            }
+        }
+    }
+
+    output.push_str("\x1b[0m");
+    output
+}
+
+/// write the sgr sequence, if it differs from the current one.
+/// None selects the terminal default
+fn push_sgr(
+    output: &mut String,
+    current: &mut Option<Option<String>>,
+    desired: Option<String>,
+    default: &str,
+) {
+    if current.as_ref() == Some(&desired) {
+        return;
+    }
+    let _ = write!(output, "\x1b[{}m", desired.as_deref().unwrap_or(default));
+    *current = Some(desired);
 }
 
 pub fn to_crossterm_color(colour: Option<ascii_assets::Color>) -> Color {
@@ -1,4 +1,10 @@
 pub mod crossterm_celldrawer;
-pub use crossterm_celldrawer::CrosstermCellDrawer;
+pub use crossterm_celldrawer::{CrosstermCellDrawer, CrosstermOptions};
+pub mod color_mode;
+pub use color_mode::ColorMode;
 pub mod test_celldrawer;
 pub use test_celldrawer::TestCellDrawer;
+pub mod asciicast_celldrawer;
+pub use asciicast_celldrawer::AsciicastCellDrawer;
+pub mod tee_celldrawer;
+pub use tee_celldrawer::{SinkFailure, TeeCellDrawer};
@@ -0,0 +1,134 @@
+use crate::{
+    DrawError,
+    terminal_buffer::{
+        CellDrawer, CursorState,
+        buffer_and_celldrawer::{
+            BatchDrawInfo, standard_celldrawer::test_celldrawer::FrameCapture,
+        },
+    },
+};
+
+/// What happens, when a sink of a TeeCellDrawer fails to flush
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SinkFailure {
+    /// the error is returned from the flush of the tee, after all other sinks have been flushed
+    #[default]
+    Propagate,
+    /// the error is logged and the sink keeps receiving commands
+    Log,
+    /// the error is logged and the sink is detached, so it never receives commands again
+    Detach,
+}
+
+#[derive(Debug)]
+struct Sink {
+    drawer: Box<dyn CellDrawer + Send>,
+    on_failure: SinkFailure,
+    detached: bool,
+}
+
+/// Drawer, that passes every command on to several other drawers,
+/// for example the terminal and an asciicast recorder at once.
+///
+/// Each sink decides by itself, how its failures are handled.
+/// Pass the tee to DefaultScreenBuffer::with_drawer
+#[derive(Debug, Default)]
+pub struct TeeCellDrawer {
+    sinks: Vec<Sink>,
+}
+
+impl TeeCellDrawer {
+    /// a tee without any sinks, that draws nowhere
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a sink, whose flush errors get returned from the tee
+    pub fn sink<CD: CellDrawer + Send + 'static>(self, drawer: CD) -> Self {
+        self.sink_with(drawer, SinkFailure::Propagate)
+    }
+
+    /// add a sink with explicit failure handling
+    pub fn sink_with<CD: CellDrawer + Send + 'static>(
+        mut self,
+        drawer: CD,
+        on_failure: SinkFailure,
+    ) -> Self {
+        self.sinks.push(Sink {
+            drawer: Box::new(drawer),
+            on_failure,
+            detached: false,
+        });
+        self
+    }
+
+    /// amount of sinks, that still receive commands
+    pub fn active_sinks(&self) -> usize {
+        self.sinks.iter().filter(|s| !s.detached).count()
+    }
+
+    fn active(&mut self) -> impl Iterator<Item = &mut Sink> {
+        self.sinks.iter_mut().filter(|s| !s.detached)
+    }
+}
+
+impl CellDrawer for TeeCellDrawer {
+    /// creates a tee without sinks, use TeeCellDrawer::sink to add them
+    fn init() -> Self {
+        Self::new()
+    }
+
+    fn set_string(&mut self, batch: BatchDrawInfo, size: (u16, u16)) {
+        for sink in self.active() {
+            sink.drawer.set_string(batch.clone(), size);
+        }
+    }
+
+    /// flushes every sink, even if one of them fails.
+    /// Returns the first error of a sink, that propagates its failures
+    fn flush(&mut self) -> Result<(), DrawError> {
+        let mut result = Ok(());
+        for (idx, sink) in self.sinks.iter_mut().enumerate() {
+            if sink.detached {
+                continue;
+            }
+            let Err(e) = sink.drawer.flush() else {
+                continue;
+            };
+            match sink.on_failure {
+                SinkFailure::Propagate => {
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+                SinkFailure::Log => log::error!("Sink {} of tee failed to flush: {}", idx, e),
+                SinkFailure::Detach => {
+                    log::error!(
+                        "Sink {} of tee failed to flush and got detached: {}",
+                        idx,
+                        e
+                    );
+                    sink.detached = true;
+                }
+            }
+        }
+        result
+    }
+
+    fn set_cursor(&mut self, cursor: CursorState) {
+        for sink in self.active() {
+            sink.drawer.set_cursor(cursor);
+        }
+    }
+
+    fn print_above(&mut self, text: &str) {
+        for sink in self.active() {
+            sink.drawer.print_above(text);
+        }
+    }
+
+    /// the capture of the first sink, that has one
+    fn capture(&self) -> Option<FrameCapture> {
+        self.sinks.iter().find_map(|s| s.drawer.capture())
+    }
+}
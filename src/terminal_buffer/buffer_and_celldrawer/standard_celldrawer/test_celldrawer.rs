@@ -1,11 +1,11 @@
-use std::sync::mpsc::Receiver;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 
-use crate::set_test_data;
 use crate::{
     DrawError,
     terminal_buffer::{
-        CellDrawer,
-        buffer_and_celldrawer::{BatchDrawInfo, screen_buffer::CellDrawerCommand},
+        CellAttributes, CellDrawer, CursorState, Grapheme, StyledChar,
+        buffer_and_celldrawer::BatchDrawInfo, grapheme::graphemes,
     },
 };
 use ascii_assets::{self, TerminalChar};
@@ -15,6 +15,12 @@ use log::info;
 pub struct TerminalContentInformation {
     pub size: (u16, u16),
     pub content: Vec<Option<TerminalChar>>,
+    /// the attributes of every cell, in the same order as the content
+    pub attributes: Vec<CellAttributes>,
+    /// the whole grapheme cluster of every cell, whose char is followed by combining chars
+    pub clusters: Vec<Option<Grapheme>>,
+    /// the cursor state at the time of the flush
+    pub cursor: CursorState,
     pub amount_of_changed_chars: usize,
     pub amount_of_draw_commands: usize,
 }
@@ -29,30 +35,115 @@ impl TerminalContentInformation {
         } else if self.content.len() > desired_len {
             self.content.truncate(desired_len);
         }
+        self.attributes.resize(desired_len, CellAttributes::NONE);
+        self.clusters.resize(desired_len, None);
+    }
+
+    /// The char of the cell together with its attributes and cluster, None for empty cells
+    pub fn styled_char(&self, idx: usize) -> Option<StyledChar> {
+        let chr = self.content.get(idx).copied().flatten()?;
+        Some(StyledChar {
+            chr,
+            attrs: self.attributes.get(idx).copied().unwrap_or_default(),
+            cluster: self.clusters.get(idx).copied().flatten(),
+        })
+    }
+}
+
+/// amount of frames, that a FrameCapture keeps by default
+pub const DEFAULT_FRAME_HISTORY: usize = 128;
+
+#[derive(Debug)]
+struct CaptureState {
+    history: VecDeque<TerminalContentInformation>,
+    history_limit: usize,
+}
+
+/// Shared handle to the frames flushed by a TestCellDrawer.
+///
+/// Every renderer gets its own capture, so multiple renderers can be tested at once
+#[derive(Debug, Clone)]
+pub struct FrameCapture {
+    state: Arc<Mutex<CaptureState>>,
+}
+
+impl Default for FrameCapture {
+    fn default() -> Self {
+        FrameCapture::new(DEFAULT_FRAME_HISTORY)
+    }
+}
+
+impl FrameCapture {
+    pub fn new(history_limit: usize) -> Self {
+        FrameCapture {
+            state: Arc::new(Mutex::new(CaptureState {
+                history: VecDeque::new(),
+                history_limit: history_limit.max(1),
+            })),
+        }
+    }
+
+    /// the content at the time of the last flush
+    pub fn latest(&self) -> Option<TerminalContentInformation> {
+        self.state.lock().unwrap().history.back().cloned()
+    }
+
+    /// all kept frames, the oldest first
+    pub fn history(&self) -> Vec<TerminalContentInformation> {
+        self.state.lock().unwrap().history.iter().cloned().collect()
+    }
+
+    /// set the amount of frames to keep, dropping the oldest ones if necessary
+    pub fn set_history_limit(&self, limit: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.history_limit = limit.max(1);
+        while state.history.len() > state.history_limit {
+            state.history.pop_front();
+        }
+    }
+
+    /// drop all frames, except the latest one
+    pub fn clear_history(&self) {
+        let mut state = self.state.lock().unwrap();
+        while state.history.len() > 1 {
+            state.history.pop_front();
+        }
+    }
+
+    fn push(&self, frame: TerminalContentInformation) {
+        let mut state = self.state.lock().unwrap();
+        if state.history.len() >= state.history_limit {
+            state.history.pop_front();
+        }
+        state.history.push_back(frame);
     }
 }
 
+/// Headless drawer, that keeps the written content in memory
 #[derive(Debug)]
 pub struct TestCellDrawer {
-    rx: Receiver<CellDrawerCommand>,
     temp_screen: TerminalContentInformation,
+    capture: FrameCapture,
 }
 
 impl CellDrawer for TestCellDrawer {
-    fn init(rx: Receiver<CellDrawerCommand>) -> Self {
+    fn init() -> Self {
         TestCellDrawer {
-            rx,
             temp_screen: TerminalContentInformation {
                 size: (0, 0),
                 content: Vec::new(),
+                attributes: Vec::new(),
+                clusters: Vec::new(),
+                cursor: CursorState::default(),
                 amount_of_changed_chars: 0,
                 amount_of_draw_commands: 0,
             },
+            capture: FrameCapture::default(),
         }
     }
 
-    fn recv(&self) -> Result<CellDrawerCommand, std::sync::mpsc::RecvError> {
-        self.rx.recv()
+    fn capture(&self) -> Option<FrameCapture> {
+        Some(self.capture.clone())
     }
 
     fn set_string(&mut self, batch: BatchDrawInfo, size: (u16, u16)) {
@@ -71,7 +162,8 @@ impl CellDrawer for TestCellDrawer {
                 seg.text, seg.fg_color, seg.bg_color, current_pos
             );
 
-            for chr in seg.text.chars() {
+            for (_, cluster, width) in graphemes(&seg.text) {
+                let chr = cluster.chars().next().unwrap_or(' ');
                 let opt_char = if chr == ' ' && current_fg.is_none() && current_bg.is_none() {
                     None
                 } else {
@@ -81,18 +173,39 @@ impl CellDrawer for TestCellDrawer {
                         bg_color: current_bg,
                     })
                 };
+                if current_pos >= self.temp_screen.content.len() {
+                    break;
+                }
 
                 self.temp_screen.content[current_pos] = opt_char;
+                self.temp_screen.attributes[current_pos] = seg.attrs;
+                self.temp_screen.clusters[current_pos] = cluster
+                    .chars()
+                    .nth(1)
+                    .is_some()
+                    .then(|| Grapheme::new(cluster));
+                // the right half of a wide char
+                for covered in
+                    current_pos + 1..(current_pos + width).min(self.temp_screen.content.len())
+                {
+                    self.temp_screen.content[covered] = None;
+                    self.temp_screen.attributes[covered] = CellAttributes::NONE;
+                    self.temp_screen.clusters[covered] = None;
+                }
 
                 self.temp_screen.amount_of_changed_chars += 1;
 
-                current_pos += 1
+                current_pos += width
             }
         }
     }
 
+    fn set_cursor(&mut self, cursor: CursorState) {
+        self.temp_screen.cursor = cursor;
+    }
+
     fn flush(&mut self) -> Result<(), DrawError> {
-        set_test_data(self.temp_screen.clone());
+        self.capture.push(self.temp_screen.clone());
         Ok(())
     }
 }
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not};
+
+use ascii_assets::TerminalChar;
+
+use crate::terminal_buffer::grapheme::{Grapheme, char_width};
+
+/// Set of text attributes of a single cell, like bold or underlined
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CellAttributes(u8);
+
+impl CellAttributes {
+    pub const NONE: CellAttributes = CellAttributes(0);
+    pub const BOLD: CellAttributes = CellAttributes(1);
+    pub const DIM: CellAttributes = CellAttributes(1 << 1);
+    pub const ITALIC: CellAttributes = CellAttributes(1 << 2);
+    pub const UNDERLINE: CellAttributes = CellAttributes(1 << 3);
+    pub const BLINK: CellAttributes = CellAttributes(1 << 4);
+    pub const REVERSE: CellAttributes = CellAttributes(1 << 5);
+    pub const STRIKETHROUGH: CellAttributes = CellAttributes(1 << 6);
+
+    /// every attribute, together with its name and the sgr codes turning it on and off
+    pub const ALL: [(CellAttributes, &'static str, u8, u8); 7] = [
+        (Self::BOLD, "bold", 1, 22),
+        (Self::DIM, "dim", 2, 22),
+        (Self::ITALIC, "italic", 3, 23),
+        (Self::UNDERLINE, "underline", 4, 24),
+        (Self::BLINK, "blink", 5, 25),
+        (Self::REVERSE, "reverse", 7, 27),
+        (Self::STRIKETHROUGH, "strikethrough", 9, 29),
+    ];
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn from_bits(bits: u8) -> Self {
+        CellAttributes(bits & 0x7f)
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: CellAttributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: CellAttributes) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: CellAttributes) {
+        self.0 &= !other.0;
+    }
+
+    pub fn toggle(&mut self, other: CellAttributes) {
+        self.0 ^= other.0;
+    }
+
+    pub fn set(&mut self, other: CellAttributes, enabled: bool) {
+        if enabled {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+
+    /// the names of all contained attributes
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        Self::ALL
+            .into_iter()
+            .filter(move |(attr, ..)| self.contains(*attr))
+            .map(|(_, name, ..)| name)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|(_, n, ..)| *n == name)
+            .map(|(attr, ..)| attr)
+    }
+
+    /// The sgr parameters, that switch a terminal from the `from` attributes to these,
+    /// without touching the colors. Empty if nothing changes
+    pub fn sgr_transition(self, from: CellAttributes) -> Vec<u8> {
+        let mut codes = Vec::new();
+        let removed = from & !self;
+        let mut added = self & !from;
+
+        // bold and dim share their off code, so the one staying has to be turned on again
+        if removed.0 & (Self::BOLD.0 | Self::DIM.0) != 0 {
+            added.insert(self & (Self::BOLD | Self::DIM));
+        }
+        for (attr, _, _, off) in Self::ALL {
+            if removed.contains(attr) && !codes.contains(&off) {
+                codes.push(off);
+            }
+        }
+        for (attr, _, on, _) in Self::ALL {
+            if added.contains(attr) {
+                codes.push(on);
+            }
+        }
+        codes
+    }
+}
+
+impl fmt::Debug for CellAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CellAttributes(")?;
+        for (i, name) in self.names().enumerate() {
+            if i > 0 {
+                write!(f, " | ")?;
+            }
+            write!(f, "{}", name)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl BitOr for CellAttributes {
+    type Output = CellAttributes;
+    fn bitor(self, rhs: Self) -> Self {
+        CellAttributes(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for CellAttributes {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for CellAttributes {
+    type Output = CellAttributes;
+    fn bitand(self, rhs: Self) -> Self {
+        CellAttributes(self.0 & rhs.0)
+    }
+}
+
+impl Not for CellAttributes {
+    type Output = CellAttributes;
+    fn not(self) -> Self {
+        CellAttributes::from_bits(!self.0)
+    }
+}
+
+/// A char together with its attributes, as it is composed and sent to the drawer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StyledChar {
+    pub chr: TerminalChar,
+    pub attrs: CellAttributes,
+    /// the whole grapheme cluster, if chr is followed by combining chars
+    pub cluster: Option<Grapheme>,
+}
+
+impl StyledChar {
+    /// the amount of cells this char takes up on the terminal
+    pub fn width(&self) -> usize {
+        match self.cluster {
+            Some(cluster) => cluster.width(),
+            None => char_width(self.chr.chr),
+        }
+    }
+
+    /// append the text of the char or its whole cluster
+    pub fn push_to(&self, out: &mut String) {
+        match self.cluster {
+            Some(cluster) => out.push_str(cluster.as_str()),
+            None => out.push(self.chr.chr),
+        }
+    }
+}
+
+impl From<TerminalChar> for StyledChar {
+    fn from(chr: TerminalChar) -> Self {
+        StyledChar {
+            chr,
+            attrs: CellAttributes::NONE,
+            cluster: None,
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use std::collections::HashMap;
-
 use ascii_assets::TerminalChar;
 
-use crate::ObjectId;
+use crate::{
+    ObjectId,
+    terminal_buffer::{CellAttributes, grapheme::Grapheme},
+};
 
 #[derive(Clone, Debug, Copy)]
 pub struct CharacterInfo {
@@ -10,9 +11,63 @@ pub struct CharacterInfo {
     pub layer: usize,
     pub screen_layer: usize,
     pub chr: TerminalChar,
+    pub attrs: CellAttributes,
+    pub cluster: Option<Grapheme>,
+    /// opacity of the object, 255 is fully opaque
+    pub alpha: u8,
+}
+
+impl CharacterInfo {
+    /// position in the stack of a cell, from the lowest to the highest
+    fn order(&self) -> (usize, usize, ObjectId) {
+        (self.screen_layer, self.layer, self.display_id)
+    }
 }
 
-#[derive(Clone, Debug)]
+/// The chars of all objects in a cell, sorted from the lowest to the highest layer.
+/// Cells rarely hold more than a few objects, so a sorted vec is cheaper than hashing
+#[derive(Clone, Debug, Default)]
 pub struct CharacterInfoList {
-    pub info: HashMap<ObjectId, CharacterInfo>,
+    stack: Vec<CharacterInfo>,
+}
+
+impl CharacterInfoList {
+    /// Insert the char of an object, replacing the one it already had in the cell
+    pub fn insert(&mut self, info: CharacterInfo) {
+        if let Some(pos) = self.position(info.display_id) {
+            if self.stack[pos].order() == info.order() {
+                self.stack[pos] = info;
+                return;
+            }
+            self.stack.remove(pos);
+        }
+        let pos = self.stack.partition_point(|i| i.order() < info.order());
+        self.stack.insert(pos, info);
+    }
+
+    pub fn remove(&mut self, obj_id: ObjectId) -> Option<CharacterInfo> {
+        let pos = self.position(obj_id)?;
+        Some(self.stack.remove(pos))
+    }
+
+    pub fn get(&self, obj_id: ObjectId) -> Option<&CharacterInfo> {
+        self.stack.iter().find(|i| i.display_id == obj_id)
+    }
+
+    /// The chars of the cell, from the lowest to the highest layer
+    pub fn layers(&self) -> &[CharacterInfo] {
+        &self.stack
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    fn position(&self, obj_id: ObjectId) -> Option<usize> {
+        self.stack.iter().position(|i| i.display_id == obj_id)
+    }
 }
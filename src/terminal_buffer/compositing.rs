@@ -0,0 +1,110 @@
+use ascii_assets::{Color, TerminalChar};
+
+use crate::{
+    ObjectId,
+    terminal_buffer::{CharacterInfo, StyledChar},
+};
+
+/// alpha of a fully opaque object
+pub const OPAQUE: u8 = u8::MAX;
+
+/// Whether the char lets everything below shine through,
+/// which is the case for a space without background and attributes
+pub fn is_transparent(info: &CharacterInfo) -> bool {
+    info.chr.chr == ' '
+        && info.chr.bg_color.is_none()
+        && info.attrs.is_empty()
+        && info.cluster.is_none()
+}
+
+/// Compose the chars of all objects in a cell, given from the lowest to the highest layer.
+///
+/// Transparent chars are skipped, chars without a background inherit the one below them
+/// and translucent objects get blended with the composed cell below.
+/// Returns the id of the highest object, that is visible in the cell
+pub fn composite(layers: &[CharacterInfo]) -> Option<(ObjectId, StyledChar)> {
+    // nothing shines through an opaque char with a background
+    let start = layers
+        .iter()
+        .rposition(|info| info.alpha == OPAQUE && info.chr.bg_color.is_some())
+        .unwrap_or(0);
+
+    let mut composed: Option<(ObjectId, StyledChar)> = None;
+    for info in layers[start..].iter().filter(|info| !is_transparent(info)) {
+        let top = StyledChar {
+            chr: info.chr,
+            attrs: info.attrs,
+            cluster: info.cluster,
+        };
+        let below = composed.map(|(_, c)| c);
+        composed = Some((info.display_id, compose_over(top, below, info.alpha)));
+    }
+    composed
+}
+
+/// put the char over the already composed cell
+fn compose_over(top: StyledChar, below: Option<StyledChar>, alpha: u8) -> StyledChar {
+    let Some(below) = below else {
+        return top;
+    };
+    let below_bg = below.chr.bg_color;
+
+    if alpha == OPAQUE {
+        return StyledChar {
+            chr: TerminalChar {
+                bg_color: top.chr.bg_color.or(below_bg),
+                ..top.chr
+            },
+            ..top
+        };
+    }
+
+    let bg = match top.chr.bg_color {
+        Some(bg) => Some(blend(bg, below_bg, alpha)),
+        None => below_bg,
+    };
+    if top.chr.chr == ' ' && top.cluster.is_none() {
+        // a translucent fill tints whatever is below it
+        let fg = match (top.chr.bg_color, below.chr.fg_color) {
+            (Some(tint), Some(fg)) => Some(blend(tint, Some(fg), alpha)),
+            (_, fg) => fg,
+        };
+        StyledChar {
+            chr: TerminalChar {
+                fg_color: fg,
+                bg_color: bg,
+                ..below.chr
+            },
+            attrs: below.attrs | top.attrs,
+            ..below
+        }
+    } else {
+        StyledChar {
+            chr: TerminalChar {
+                fg_color: top.chr.fg_color.map(|fg| blend(fg, below_bg, alpha)),
+                bg_color: bg,
+                ..top.chr
+            },
+            ..top
+        }
+    }
+}
+
+/// Blend the color over the one below it.
+/// Only rgb colors can be blended, otherwise the top color is kept
+pub fn blend(top: Color, below: Option<Color>, alpha: u8) -> Color {
+    match below {
+        Some(below) if !top.reset && !below.reset => {
+            let mix = |t: u8, b: u8| {
+                ((t as u32 * alpha as u32 + b as u32 * (OPAQUE - alpha) as u32) / OPAQUE as u32)
+                    as u8
+            };
+            Color::rgb(
+                mix(top.rgb.0, below.rgb.0),
+                mix(top.rgb.1, below.rgb.1),
+                mix(top.rgb.2, below.rgb.2),
+            )
+        }
+        _ => top,
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt::Write;
+
+/// Shape of the hardware cursor, set through DECSCUSR
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    /// whatever the user configured in their terminal
+    #[default]
+    Default,
+    Block,
+    Underline,
+    Bar,
+}
+
+impl CursorShape {
+    /// the DECSCUSR parameter selecting this shape
+    pub fn decscusr_param(self, blinking: bool) -> u8 {
+        let steady = !blinking as u8;
+        match self {
+            CursorShape::Default => 0,
+            CursorShape::Block => 1 + steady,
+            CursorShape::Underline => 3 + steady,
+            CursorShape::Bar => 5 + steady,
+        }
+    }
+}
+
+/// The state of the hardware cursor, as it should be after a flush.
+/// The position is absolute and zero based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CursorState {
+    pub visible: bool,
+    pub position: (u16, u16),
+    pub shape: CursorShape,
+    pub blinking: bool,
+}
+
+impl CursorState {
+    /// The escape sequences moving the cursor into place and applying its shape and visibility
+    pub fn escape_sequence(&self) -> String {
+        if !self.visible {
+            return String::from("\x1b[?25l");
+        }
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "\x1b[{} q\x1b[{};{}H\x1b[?25h",
+            self.shape.decscusr_param(self.blinking),
+            self.position.1 + 1,
+            self.position.0 + 1
+        );
+        out
+    }
+}
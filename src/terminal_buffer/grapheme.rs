@@ -0,0 +1,87 @@
+use std::fmt;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// the most bytes a grapheme cluster can take up, longer clusters get cut at a char boundary
+pub const MAX_GRAPHEME_LEN: usize = 31;
+
+/// A grapheme cluster, like a char with combining marks or an emoji sequence.
+///
+/// Stored inline, so cells stay Copy
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grapheme {
+    len: u8,
+    bytes: [u8; MAX_GRAPHEME_LEN],
+}
+
+impl Grapheme {
+    pub fn new(cluster: &str) -> Self {
+        let mut len = 0;
+        for (idx, c) in cluster.char_indices() {
+            let end = idx + c.len_utf8();
+            if end > MAX_GRAPHEME_LEN {
+                break;
+            }
+            len = end;
+        }
+        let mut bytes = [0; MAX_GRAPHEME_LEN];
+        bytes[..len].copy_from_slice(&cluster.as_bytes()[..len]);
+        Grapheme {
+            len: len as u8,
+            bytes,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only ever filled with whole chars of a str
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+
+    /// the first char of the cluster
+    pub fn base(&self) -> char {
+        self.as_str().chars().next().unwrap_or(' ')
+    }
+
+    pub fn width(&self) -> usize {
+        str_width(self.as_str())
+    }
+}
+
+impl fmt::Debug for Grapheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Grapheme({:?})", self.as_str())
+    }
+}
+
+impl fmt::Display for Grapheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The amount of cells a char takes up on the terminal, either 1 or 2.
+/// Control and zero width chars still get a cell of their own
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(1).clamp(1, 2)
+}
+
+/// The amount of cells a grapheme cluster takes up on the terminal, either 1 or 2
+pub fn str_width(cluster: &str) -> usize {
+    match cluster.chars().count() {
+        0 => 1,
+        1 => char_width(cluster.chars().next().unwrap_or(' ')),
+        _ => cluster.width().clamp(1, 2),
+    }
+}
+
+/// The grapheme clusters of the text, together with their byte index and width
+pub fn graphemes(text: &str) -> impl Iterator<Item = (usize, &str, usize)> {
+    text.grapheme_indices(true)
+        .map(|(idx, cluster)| (idx, cluster, str_width(cluster)))
+}
+
+/// The amount of cells the text takes up on the terminal
+pub fn text_width(text: &str) -> usize {
+    graphemes(text).map(|(_, _, width)| width).sum()
+}
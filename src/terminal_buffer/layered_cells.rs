@@ -0,0 +1,141 @@
+use std::{collections::HashMap, ops::Index, ops::Range, slice};
+
+use crate::{
+    ObjectId,
+    terminal_buffer::{CharacterInfo, CharacterInfoList, StyledChar, compositing},
+};
+
+/// The cells of a screen buffer, each holding the chars of the objects drawn to it.
+///
+/// Every object remembers the runs of cells it has been drawn to,
+/// so redrawing or removing it only touches its own cells
+#[derive(Debug, Clone, Default)]
+pub struct LayeredCells {
+    cells: Vec<CharacterInfoList>,
+    spans: HashMap<ObjectId, Vec<Range<usize>>>,
+    /// one bit per cell, reused between calls of `place`
+    touched: Vec<u64>,
+}
+
+impl LayeredCells {
+    pub fn new(capacity: usize) -> Self {
+        let mut cells = Vec::new();
+        cells.resize_with(capacity, CharacterInfoList::default);
+        Self {
+            cells,
+            spans: HashMap::new(),
+            touched: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, CharacterInfoList> {
+        self.cells.iter()
+    }
+
+    /// Drop every object and change the amount of cells
+    pub fn reset(&mut self, capacity: usize) {
+        self.cells.clear();
+        self.cells.resize_with(capacity, CharacterInfoList::default);
+        self.spans.clear();
+    }
+
+    /// The runs of cells, the object has been drawn to
+    pub fn spans(&self, obj_id: ObjectId) -> &[Range<usize>] {
+        self.spans
+            .get(&obj_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The composed char of the cell, together with the highest visible object
+    pub fn top(&self, idx: usize) -> Option<(ObjectId, StyledChar)> {
+        compositing::composite(self.cells[idx].layers())
+    }
+
+    /// Replace everything the object has drawn with the given chars, keyed by cell index.
+    /// Later chars win over earlier ones in the same cell.
+    ///
+    /// Returns the cells, the object has been drawn to before, but not anymore
+    pub fn place(
+        &mut self,
+        obj_id: ObjectId,
+        chars: impl IntoIterator<Item = (usize, CharacterInfo)>,
+    ) -> Vec<Range<usize>> {
+        // drawables hand out their chars in any order, so the touched cells get
+        // marked in a bitset instead of sorting them
+        self.touched.clear();
+        self.touched.resize(self.cells.len().div_ceil(64), 0);
+        for (idx, info) in chars {
+            if let Some(cell) = self.cells.get_mut(idx) {
+                cell.insert(info);
+                self.touched[idx / 64] |= 1 << (idx % 64);
+            }
+        }
+
+        let mut vacated = Vec::new();
+        let old = self.spans.remove(&obj_id).unwrap_or_default();
+        for idx in old.into_iter().flatten() {
+            if idx < self.cells.len() && self.touched[idx / 64] & (1 << (idx % 64)) == 0 {
+                self.cells[idx].remove(obj_id);
+                push_idx(&mut vacated, idx);
+            }
+        }
+
+        let mut spans = Vec::new();
+        for (word_idx, &word) in self.touched.iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                push_idx(&mut spans, word_idx * 64 + word.trailing_zeros() as usize);
+                word &= word - 1;
+            }
+        }
+        if !spans.is_empty() {
+            self.spans.insert(obj_id, spans);
+        }
+        vacated
+    }
+
+    /// Remove the object from every cell, returning the cells it has been drawn to
+    pub fn remove(&mut self, obj_id: ObjectId) -> Vec<Range<usize>> {
+        let spans = self.spans.remove(&obj_id).unwrap_or_default();
+        for idx in spans.iter().cloned().flatten() {
+            if let Some(cell) = self.cells.get_mut(idx) {
+                cell.remove(obj_id);
+            }
+        }
+        spans
+    }
+}
+
+impl Index<usize> for LayeredCells {
+    type Output = CharacterInfoList;
+
+    fn index(&self, idx: usize) -> &CharacterInfoList {
+        &self.cells[idx]
+    }
+}
+
+impl<'a> IntoIterator for &'a LayeredCells {
+    type Item = &'a CharacterInfoList;
+    type IntoIter = slice::Iter<'a, CharacterInfoList>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+/// extend the last run by the index or start a new one
+fn push_idx(spans: &mut Vec<Range<usize>>, idx: usize) {
+    match spans.last_mut() {
+        Some(last) if last.end == idx => last.end += 1,
+        _ => spans.push(idx..idx + 1),
+    }
+}
@@ -4,5 +4,19 @@ pub use standard_drawables::{LineDrawable, RectDrawable, SpriteDrawable};
 pub mod character_info;
 pub use character_info::{CharacterInfo, CharacterInfoList};
 
+pub mod layered_cells;
+pub use layered_cells::LayeredCells;
+
+pub mod grapheme;
+pub use grapheme::Grapheme;
+
+pub mod cell_attributes;
+pub use cell_attributes::{CellAttributes, StyledChar};
+
+pub mod compositing;
+
+pub mod cursor;
+pub use cursor::{CursorShape, CursorState};
+
 pub mod buffer_and_celldrawer;
 pub use buffer_and_celldrawer::{CellDrawer, ScreenBuffer, ScreenBufferCore};
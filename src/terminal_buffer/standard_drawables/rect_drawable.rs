@@ -5,7 +5,6 @@ use crate::{
 };
 use ascii_assets::TerminalChar;
 use common_stdx::{Point, Rect};
-use log::info;
 
 #[derive(Clone, Debug)]
 pub enum BorderStyle {
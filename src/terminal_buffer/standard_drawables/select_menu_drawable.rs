@@ -11,7 +11,7 @@ use common_stdx::{Point, Rect};
 use crossterm::event::KeyCode;
 
 use crate::{
-    DrawError, Drawable, ScreenFitting, ScreenKey,
+    DrawError, Drawable, ScreenFitting, ScreenKey, SharedClock,
     error::AppError,
     input_handler::{
         hook::EventHook,
@@ -146,6 +146,11 @@ impl Drawable for SelectMenuDrawable {
         }
         Ok(initial)
     }
+    fn attach_clock(&mut self, clock: SharedClock) {
+        for field in &mut self.fields {
+            field.attach_clock(clock.clone());
+        }
+    }
     fn as_screen_fitting_mut(&mut self) -> Option<&mut dyn crate::ScreenFitting> {
         Some(self)
     }
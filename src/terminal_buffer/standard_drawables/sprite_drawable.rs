@@ -1,7 +1,7 @@
 use common_stdx::{Point, Rect};
 
 use crate::{
-    DrawError, Drawable, SinglePointed, SpriteEntry, SpriteId, SpriteRegistry,
+    DrawError, Drawable, SharedClock, SinglePointed, SpriteEntry, SpriteId, SpriteRegistry,
     drawable_traits::basic_draw_creator::BasicDrawCreator,
     update_interval_handler::UpdateIntervalCreator,
 };
@@ -56,6 +56,7 @@ pub struct SpriteDrawable {
     pub sprite_id: SpriteId,
     pub last_state_change: std::time::Instant,
     pub animation_type: AnimationInfo,
+    pub clock: SharedClock,
 }
 
 impl SinglePointed for SpriteDrawable {
@@ -107,7 +108,11 @@ impl SpriteDrawable {
     }
 
     fn get_frames_since_last_update(&self, speed: &VideoSpeed) -> u16 {
-        let elapsed_ms = self.last_state_change.elapsed().as_millis();
+        let elapsed_ms = self
+            .clock
+            .now()
+            .saturating_duration_since(self.last_state_change)
+            .as_millis();
         match speed {
             VideoSpeed::Fps(fps) => ((elapsed_ms * (*fps as u128)) / 1000) as u16,
             VideoSpeed::MillisecondsPerFrame(ms) => (elapsed_ms / (*ms as u128)) as u16,
@@ -122,23 +127,30 @@ impl Drawable for SpriteDrawable {
             .ok_or(DrawError::SpriteNotFound(self.sprite_id))?;
 
         let size = sprite.info.size();
-        Ok((size.1 as u16, size.2 as u16))
+        Ok((size.2 as u16, size.1 as u16))
     }
     fn as_single_pointed_mut(&mut self) -> Option<&mut dyn SinglePointed> {
         Some(self)
     }
+    fn attach_clock(&mut self, clock: SharedClock) {
+        if clock != self.clock {
+            // the animation restarts, since the times of both clocks are unrelated
+            self.last_state_change = clock.now();
+            self.clock = clock;
+        }
+    }
     fn draw(&mut self, sprites: &SpriteRegistry) -> Result<BasicDrawCreator, DrawError> {
         let sprite = sprites
             .get(&self.sprite_id)
             .ok_or(DrawError::SpriteNotFound(self.sprite_id))?;
 
         let size = sprite.info.size();
-        let mut bd_creator = BasicDrawCreator::new_with_capacity(size.0 * size.1);
+        let mut bd_creator = BasicDrawCreator::new_with_capacity(size.1 * size.2);
         match &self.animation_type {
             AnimationInfo::Image { frame } => {
                 let frame_idx = self.get_frame_idx(frame, sprite);
 
-                self.draw_frame(sprite, frame_idx, (size.1, size.2), &mut bd_creator)?;
+                self.draw_frame(sprite, frame_idx, (size.2, size.1), &mut bd_creator)?;
             }
             AnimationInfo::Video {
                 loop_type,
@@ -183,7 +195,7 @@ impl Drawable for SpriteDrawable {
                 if let Some(frame_in_range) = current_frame_in_range {
                     let frame_to_draw = real_start_frame + frame_in_range as usize;
                     let size = sprite.info.size();
-                    self.draw_frame(sprite, frame_to_draw, (size.1, size.2), &mut bd_creator)?;
+                    self.draw_frame(sprite, frame_to_draw, (size.2, size.1), &mut bd_creator)?;
                 }
             }
         }
@@ -199,8 +211,8 @@ impl Drawable for SpriteDrawable {
         c.register_redraw_region(Rect {
             p1: self.position,
             p2: Point {
-                x: self.position.x + size.1 as i32,
-                y: self.position.y + size.2 as i32,
+                x: self.position.x + size.2 as i32,
+                y: self.position.y + size.1 as i32,
             },
         });
         Some(c)
@@ -1,8 +1,8 @@
 use std::ops::Range;
 
 use crate::{
-    DoublePointed, DrawError, Drawable, SpriteRegistry,
-    drawable_traits::basic_draw_creator::BasicDrawCreator,
+    CellAttributes, DoublePointed, DrawError, Drawable, SpriteRegistry,
+    drawable_traits::basic_draw_creator::BasicDrawCreator, terminal_buffer::grapheme::graphemes,
     update_interval_handler::UpdateIntervalCreator,
 };
 use ascii_assets::{Color, TerminalChar};
@@ -21,6 +21,26 @@ pub struct TextStyle {
     pub background: Option<Color>,
     pub bold: Option<bool>,
     pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub reverse: Option<bool>,
+    pub strikethrough: Option<bool>,
+}
+
+impl TextStyle {
+    /// the cell attributes enabled by this style
+    pub fn attributes(&self) -> CellAttributes {
+        let mut attrs = CellAttributes::NONE;
+        for (enabled, attr) in [
+            (self.bold, CellAttributes::BOLD),
+            (self.italic, CellAttributes::ITALIC),
+            (self.underline, CellAttributes::UNDERLINE),
+            (self.reverse, CellAttributes::REVERSE),
+            (self.strikethrough, CellAttributes::STRIKETHROUGH),
+        ] {
+            attrs.set(attr, enabled.unwrap_or(false));
+        }
+        attrs
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -82,17 +102,26 @@ impl Drawable for TextDrawable {
         let mut visual_row = 0;
 
         for line in self.lines.iter().skip(self.scroll_y as usize) {
-            // carry both char and original index in line.text
-            let indexed_chars: Vec<(usize, char)> = line.text.char_indices().collect();
+            // carry the grapheme clusters together with their original index in line.text and their width
+            let clusters: Vec<(usize, &str, usize)> = graphemes(&line.text).collect();
 
-            // wrap into chunks of (usize, char)
+            // wrap by display width, so wide chars never get split
             let wrapped_lines = if self.wrapping && max_width > 0 {
-                indexed_chars
-                    .chunks(max_width)
-                    .map(|chunk| chunk.to_vec())
-                    .collect::<Vec<_>>()
+                let mut wrapped: Vec<Vec<(usize, &str, usize)>> = vec![Vec::new()];
+                let mut row_width = 0;
+                for cluster in clusters {
+                    if row_width + cluster.2 > max_width && row_width > 0 {
+                        wrapped.push(Vec::new());
+                        row_width = 0;
+                    }
+                    row_width += cluster.2;
+                    if let Some(row) = wrapped.last_mut() {
+                        row.push(cluster);
+                    }
+                }
+                wrapped
             } else {
-                vec![indexed_chars]
+                vec![clusters]
             };
 
             for visual in wrapped_lines {
@@ -101,7 +130,10 @@ impl Drawable for TextDrawable {
                 }
 
                 let y = self.area.p1.y + visual_row as i32;
-                let line_len = visual.len() as i32;
+                let line_len = visual
+                    .iter()
+                    .map(|(_, _, width)| *width as i32)
+                    .sum::<i32>();
                 let mut x = self.area.p1.x;
 
                 match line.alignment {
@@ -114,8 +146,8 @@ impl Drawable for TextDrawable {
                     }
                 }
 
-                for &(idx, ch) in &visual {
-                    if x >= self.area.p2.x {
+                for &(idx, cluster, width) in &visual {
+                    if x + width as i32 > self.area.p2.x {
                         break;
                     }
                     if x >= self.area.p1.x {
@@ -127,20 +159,16 @@ impl Drawable for TextDrawable {
                             .map(|s| &s.style)
                             .unwrap_or(&line.default_style);
 
-                        let mut tc = TerminalChar::from_char(ch);
+                        let mut tc = TerminalChar::from_char(' ');
                         if let Some(fg) = style.foreground {
                             tc.fg_color = Some(fg);
                         }
                         if let Some(bg) = style.background {
                             tc.bg_color = Some(bg);
                         }
-                        // todo: add bold and italic _somehow_
-                        // tc.bold = style.bold.unwrap_or(false);
-                        // tc.italic = style.italic.unwrap_or(false);
-
-                        out.draw_char((x, y), tc);
+                        out.draw_grapheme((x, y), cluster, tc, style.attributes());
                     }
-                    x += 1;
+                    x += width as i32;
                 }
 
                 visual_row += 1;
@@ -0,0 +1,5 @@
+pub mod snapshot;
+pub use snapshot::{
+    CellColor, CellDiff, Snapshot, SnapshotCell, UPDATE_SNAPSHOTS_ENV, assert_frame_matches,
+    check_frame, compare_frame,
+};
@@ -0,0 +1,526 @@
+use std::fmt::{self, Write as FmtWrite};
+use std::path::Path;
+
+use ascii_assets::{Color, TerminalChar};
+
+use crate::error::SnapshotError;
+use crate::terminal_buffer::CellAttributes;
+use crate::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::TerminalContentInformation;
+
+/// if set, mismatching or missing fixtures get overwritten instead of failing
+pub const UPDATE_SNAPSHOTS_ENV: &str = "LTRENDER_UPDATE_SNAPSHOTS";
+
+const HEADER: &str = "ltrender snapshot";
+/// marks a cell without a color in the fg and bg grids
+const NO_COLOR: char = '.';
+/// chars used to refer to the colors in the legend
+const LEGEND_KEYS: &str =
+    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!#$%&*+-/:;<=>?@^_~";
+/// the amount of differing cells listed in a report
+const MAX_REPORTED_CELLS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellColor {
+    Reset,
+    Rgb(u8, u8, u8),
+}
+
+impl From<Color> for CellColor {
+    fn from(c: Color) -> Self {
+        if c.reset {
+            CellColor::Reset
+        } else {
+            CellColor::Rgb(c.rgb.0, c.rgb.1, c.rgb.2)
+        }
+    }
+}
+
+impl fmt::Display for CellColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellColor::Reset => write!(f, "reset"),
+            CellColor::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
+
+impl CellColor {
+    fn parse(s: &str) -> Option<Self> {
+        if s == "reset" {
+            return Some(CellColor::Reset);
+        }
+        let hex = s.strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some(CellColor::Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotCell {
+    pub chr: char,
+    pub fg: Option<CellColor>,
+    pub bg: Option<CellColor>,
+    pub attrs: CellAttributes,
+}
+
+impl SnapshotCell {
+    pub const EMPTY: SnapshotCell = SnapshotCell {
+        chr: ' ',
+        fg: None,
+        bg: None,
+        attrs: CellAttributes::NONE,
+    };
+}
+
+impl From<Option<TerminalChar>> for SnapshotCell {
+    fn from(chr: Option<TerminalChar>) -> Self {
+        match chr {
+            Some(c) => SnapshotCell {
+                chr: c.chr,
+                fg: c.fg_color.map(Into::into),
+                bg: c.bg_color.map(Into::into),
+                attrs: CellAttributes::NONE,
+            },
+            None => SnapshotCell::EMPTY,
+        }
+    }
+}
+
+impl fmt::Display for SnapshotCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let color = |c: Option<CellColor>| c.map_or(String::from("-"), |c| c.to_string());
+        write!(
+            f,
+            "{:?} fg={} bg={}",
+            self.chr,
+            color(self.fg),
+            color(self.bg)
+        )?;
+        if !self.attrs.is_empty() {
+            write!(
+                f,
+                " attrs={}",
+                self.attrs.names().collect::<Vec<_>>().join("+")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A single differing cell between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff {
+    pub pos: (u16, u16),
+    pub expected: SnapshotCell,
+    pub found: SnapshotCell,
+}
+
+/// A frame in a form, that can be written to and compared against fixture files.
+///
+/// The text format consists of a glyph grid framed by `|`,
+/// an fg and a bg grid referring to the colors in the legend,
+/// where colorless cells are marked with a `.`,
+/// and an attrs grid referring to the attribute sets in the attr legend.
+/// Grids without any colors or attributes are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub size: (u16, u16),
+    pub cells: Vec<SnapshotCell>,
+}
+
+impl From<&TerminalContentInformation> for Snapshot {
+    fn from(frame: &TerminalContentInformation) -> Self {
+        let len = frame.size.0 as usize * frame.size.1 as usize;
+        let mut cells: Vec<SnapshotCell> = frame
+            .content
+            .iter()
+            .take(len)
+            .enumerate()
+            .map(|(i, c)| SnapshotCell {
+                attrs: frame.attributes.get(i).copied().unwrap_or_default(),
+                ..SnapshotCell::from(*c)
+            })
+            .collect();
+        cells.resize(len, SnapshotCell::EMPTY);
+        Snapshot {
+            size: frame.size,
+            cells,
+        }
+    }
+}
+
+impl Snapshot {
+    pub fn cell(&self, x: u16, y: u16) -> Option<&SnapshotCell> {
+        if x >= self.size.0 {
+            return None;
+        }
+        self.cells
+            .get(y as usize * self.size.0 as usize + x as usize)
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[SnapshotCell]> {
+        self.cells.chunks(self.size.0.max(1) as usize)
+    }
+
+    /// serialize into the human readable fixture format
+    pub fn to_text(&self) -> Result<String, SnapshotError> {
+        let mut legend: Vec<CellColor> = Vec::new();
+        for cell in &self.cells {
+            for color in [cell.fg, cell.bg].into_iter().flatten() {
+                if !legend.contains(&color) {
+                    legend.push(color);
+                }
+            }
+        }
+        let keys: Vec<char> = LEGEND_KEYS.chars().collect();
+        if legend.len() > keys.len() {
+            return Err(SnapshotError::TooManyColors {
+                found: legend.len(),
+                max: keys.len(),
+            });
+        }
+        let key_of = |color: Option<CellColor>| match color {
+            Some(c) => keys[legend.iter().position(|l| *l == c).unwrap_or_default()],
+            None => NO_COLOR,
+        };
+
+        let mut attr_legend: Vec<CellAttributes> = Vec::new();
+        for cell in &self.cells {
+            if !cell.attrs.is_empty() && !attr_legend.contains(&cell.attrs) {
+                attr_legend.push(cell.attrs);
+            }
+        }
+        let attr_key_of = |attrs: CellAttributes| match attr_legend.iter().position(|a| *a == attrs)
+        {
+            Some(i) => keys[i],
+            None => NO_COLOR,
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{} {}x{}", HEADER, self.size.0, self.size.1);
+        out.push_str("glyphs:\n");
+        for row in self.rows() {
+            out.push('|');
+            out.extend(row.iter().map(|c| printable(c.chr)));
+            out.push_str("|\n");
+        }
+        if self.cells.iter().any(|c| c.fg.is_some()) {
+            out.push_str("fg:\n");
+            for row in self.rows() {
+                out.push('|');
+                out.extend(row.iter().map(|c| key_of(c.fg)));
+                out.push_str("|\n");
+            }
+        }
+        if self.cells.iter().any(|c| c.bg.is_some()) {
+            out.push_str("bg:\n");
+            for row in self.rows() {
+                out.push('|');
+                out.extend(row.iter().map(|c| key_of(c.bg)));
+                out.push_str("|\n");
+            }
+        }
+        if !attr_legend.is_empty() {
+            out.push_str("attrs:\n");
+            for row in self.rows() {
+                out.push('|');
+                out.extend(row.iter().map(|c| attr_key_of(c.attrs)));
+                out.push_str("|\n");
+            }
+        }
+        if !legend.is_empty() {
+            out.push_str("legend:\n");
+            for (key, color) in keys.iter().zip(&legend) {
+                let _ = writeln!(out, "{} {}", key, color);
+            }
+        }
+        if !attr_legend.is_empty() {
+            out.push_str("attr legend:\n");
+            for (key, attrs) in keys.iter().zip(&attr_legend) {
+                let _ = writeln!(
+                    out,
+                    "{} {}",
+                    key,
+                    attrs.names().collect::<Vec<_>>().join(" ")
+                );
+            }
+        }
+        Ok(out)
+    }
+
+    /// parse the fixture format written by to_text
+    pub fn parse(text: &str) -> Result<Self, SnapshotError> {
+        let mut lines = text.lines().enumerate().map(|(i, l)| (i + 1, l));
+        let parse_err = |line: usize, message: &str| SnapshotError::Parse {
+            line,
+            message: message.to_string(),
+        };
+
+        let (_, header) = lines.next().ok_or(parse_err(1, "empty snapshot"))?;
+        let size = header
+            .strip_prefix(HEADER)
+            .map(str::trim)
+            .and_then(|s| s.split_once('x'))
+            .and_then(|(w, h)| Some((w.parse::<u16>().ok()?, h.parse::<u16>().ok()?)))
+            .ok_or(parse_err(
+                1,
+                "expected `ltrender snapshot <width>x<height>`",
+            ))?;
+
+        let mut glyphs: Vec<Vec<char>> = Vec::new();
+        let mut fg: Vec<Vec<char>> = Vec::new();
+        let mut bg: Vec<Vec<char>> = Vec::new();
+        let mut attrs: Vec<Vec<char>> = Vec::new();
+        let mut legend: Vec<(char, CellColor)> = Vec::new();
+        let mut attr_legend: Vec<(char, CellAttributes)> = Vec::new();
+        let mut section = "";
+
+        for (nr, line) in lines {
+            match line {
+                "glyphs:" | "fg:" | "bg:" | "attrs:" | "legend:" | "attr legend:" => {
+                    section = line;
+                    continue;
+                }
+                "" => continue,
+                _ => {}
+            }
+            match section {
+                "glyphs:" | "fg:" | "bg:" | "attrs:" => {
+                    let row = line
+                        .strip_prefix('|')
+                        .and_then(|l| l.strip_suffix('|'))
+                        .ok_or(parse_err(nr, "rows have to be framed by `|`"))?;
+                    let row: Vec<char> = row.chars().collect();
+                    if row.len() != size.0 as usize {
+                        return Err(parse_err(nr, "row length does not match the width"));
+                    }
+                    match section {
+                        "glyphs:" => glyphs.push(row),
+                        "fg:" => fg.push(row),
+                        "bg:" => bg.push(row),
+                        _ => attrs.push(row),
+                    }
+                }
+                "legend:" => {
+                    let mut chars = line.chars();
+                    let key = chars.next().ok_or(parse_err(nr, "missing legend key"))?;
+                    let color = CellColor::parse(chars.as_str().trim())
+                        .ok_or(parse_err(nr, "expected `reset` or `#rrggbb`"))?;
+                    legend.push((key, color));
+                }
+                "attr legend:" => {
+                    let mut chars = line.chars();
+                    let key = chars.next().ok_or(parse_err(nr, "missing legend key"))?;
+                    let mut set = CellAttributes::NONE;
+                    for name in chars.as_str().split_whitespace() {
+                        set.insert(
+                            CellAttributes::from_name(name)
+                                .ok_or(parse_err(nr, "unknown attribute"))?,
+                        );
+                    }
+                    attr_legend.push((key, set));
+                }
+                _ => return Err(parse_err(nr, "content outside of a section")),
+            }
+        }
+
+        let height = size.1 as usize;
+        if glyphs.len() != height
+            || (!fg.is_empty() && fg.len() != height)
+            || (!bg.is_empty() && bg.len() != height)
+            || (!attrs.is_empty() && attrs.len() != height)
+        {
+            return Err(parse_err(0, "amount of rows does not match the height"));
+        }
+
+        let lookup = |key: char| -> Result<Option<CellColor>, SnapshotError> {
+            if key == NO_COLOR {
+                return Ok(None);
+            }
+            legend
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, c)| Some(*c))
+                .ok_or(SnapshotError::Parse {
+                    line: 0,
+                    message: format!("color `{}` is missing in the legend", key),
+                })
+        };
+
+        let attr_lookup = |key: char| -> Result<CellAttributes, SnapshotError> {
+            if key == NO_COLOR {
+                return Ok(CellAttributes::NONE);
+            }
+            attr_legend
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, a)| *a)
+                .ok_or(SnapshotError::Parse {
+                    line: 0,
+                    message: format!("attributes `{}` are missing in the attr legend", key),
+                })
+        };
+
+        let mut cells = Vec::with_capacity(size.0 as usize * height);
+        for (y, row) in glyphs.iter().enumerate() {
+            for (x, chr) in row.iter().enumerate() {
+                cells.push(SnapshotCell {
+                    chr: *chr,
+                    fg: fg.get(y).map_or(Ok(None), |row| lookup(row[x]))?,
+                    bg: bg.get(y).map_or(Ok(None), |row| lookup(row[x]))?,
+                    attrs: attrs
+                        .get(y)
+                        .map_or(Ok(CellAttributes::NONE), |row| attr_lookup(row[x]))?,
+                });
+            }
+        }
+        Ok(Snapshot { size, cells })
+    }
+
+    /// render the frame with ansi escape codes, to view it in a terminal
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.rows() {
+            let mut current = (None, None, CellAttributes::NONE);
+            out.push('|');
+            for cell in row {
+                if (cell.fg, cell.bg, cell.attrs) != current {
+                    out.push_str("\x1b[0m");
+                    for code in cell.attrs.sgr_transition(CellAttributes::NONE) {
+                        let _ = write!(out, "\x1b[{}m", code);
+                    }
+                    if let Some(CellColor::Rgb(r, g, b)) = cell.fg {
+                        let _ = write!(out, "\x1b[38;2;{};{};{}m", r, g, b);
+                    }
+                    if let Some(CellColor::Rgb(r, g, b)) = cell.bg {
+                        let _ = write!(out, "\x1b[48;2;{};{};{}m", r, g, b);
+                    }
+                    current = (cell.fg, cell.bg, cell.attrs);
+                }
+                out.push(printable(cell.chr));
+            }
+            out.push_str("\x1b[0m|\n");
+        }
+        out
+    }
+
+    /// all cells, that differ from the expected snapshot
+    pub fn diff(&self, expected: &Snapshot) -> Vec<CellDiff> {
+        let width = self.size.0.max(expected.size.0);
+        let height = self.size.1.max(expected.size.1);
+        let mut diffs = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let e = expected.cell(x, y).copied().unwrap_or(SnapshotCell::EMPTY);
+                let f = self.cell(x, y).copied().unwrap_or(SnapshotCell::EMPTY);
+                if e != f {
+                    diffs.push(CellDiff {
+                        pos: (x, y),
+                        expected: e,
+                        found: f,
+                    });
+                }
+            }
+        }
+        diffs
+    }
+
+    /// a human readable description of how this snapshot differs from the expected one
+    pub fn diff_report(&self, expected: &Snapshot) -> String {
+        let diffs = self.diff(expected);
+        let mut out = String::new();
+        if self.size != expected.size {
+            let _ = writeln!(
+                out,
+                "size differs: expected {}x{}, found {}x{}",
+                expected.size.0, expected.size.1, self.size.0, self.size.1
+            );
+        }
+        let _ = writeln!(out, "{} cells differ", diffs.len());
+        for d in diffs.iter().take(MAX_REPORTED_CELLS) {
+            let _ = writeln!(
+                out,
+                "  ({}, {}): expected {}, found {}",
+                d.pos.0, d.pos.1, d.expected, d.found
+            );
+        }
+        if diffs.len() > MAX_REPORTED_CELLS {
+            let _ = writeln!(out, "  ... {} more", diffs.len() - MAX_REPORTED_CELLS);
+        }
+        let _ = write!(
+            out,
+            "expected:\n{}found:\n{}",
+            expected.to_ansi(),
+            self.to_ansi()
+        );
+        out
+    }
+}
+
+fn printable(chr: char) -> char {
+    if chr.is_control() { '\u{FFFD}' } else { chr }
+}
+
+fn update_requested() -> bool {
+    std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Compare the frame against the fixture at the path.
+///
+/// If the env var LTRENDER_UPDATE_SNAPSHOTS is set, the fixture gets (over)written instead
+pub fn check_frame(
+    frame: &TerminalContentInformation,
+    path: impl AsRef<Path>,
+) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+    let found = Snapshot::from(frame);
+
+    if update_requested() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, found.to_text()?)?;
+        return Ok(());
+    }
+    compare_frame(frame, path)
+}
+
+/// Compare the frame against the fixture at the path, without ever writing it
+pub fn compare_frame(
+    frame: &TerminalContentInformation,
+    path: impl AsRef<Path>,
+) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+    let found = Snapshot::from(frame);
+
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(SnapshotError::Missing {
+                path: path.display().to_string(),
+                snapshot: found.to_text()?,
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let expected = Snapshot::parse(&text)?;
+    if found == expected {
+        Ok(())
+    } else {
+        Err(SnapshotError::Mismatch {
+            path: path.display().to_string(),
+            report: found.diff_report(&expected),
+        })
+    }
+}
+
+/// Like check_frame, but panics with the diff report on mismatch
+#[track_caller]
+pub fn assert_frame_matches(frame: &TerminalContentInformation, path: impl AsRef<Path>) {
+    if let Err(e) = check_frame(frame, path) {
+        panic!("{}", e);
+    }
+}
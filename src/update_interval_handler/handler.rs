@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use crate::update_interval_handler::UpdateIntervalCreator;
 
@@ -67,6 +67,20 @@ impl UpdateIntervalHandler {
         }
     }
 
+    /// Mark runs of cells as dirty, given as indices into the buffer
+    pub fn merge_cell_ranges(&mut self, ranges: impl IntoIterator<Item = Range<usize>>) {
+        let max_idx = self.width as usize * self.height as usize;
+        for range in ranges {
+            let end = range.end.min(max_idx);
+            if range.start < end {
+                self.intervals.push(UpdateInterval {
+                    interval: (range.start, end),
+                    iv_type: UpdateIntervalType::Optimized,
+                });
+            }
+        }
+    }
+
     pub fn merge_redraw_regions(&mut self, hash: HashMap<u16, Vec<UpdateInterval>>) {
         let width = self.width as usize;
         let height = self.height as usize;
@@ -0,0 +1,125 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ascii_assets::{Color, TerminalChar};
+use common_stdx::{Point, Rect};
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CellAttributes;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    AsciicastCellDrawer, BatchDrawInfo, BatchSegment, ColorMode, CrosstermOptions,
+    DefaultScreenBuffer,
+};
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, InlineRegion, ManualClock, Renderer, SharedClock};
+
+use common::SharedWriter;
+
+#[test]
+fn flushes_become_timestamped_events() -> Result<(), AppError> {
+    let out = SharedWriter::default();
+    let clock = Arc::new(ManualClock::default());
+    let drawer = AsciicastCellDrawer::new(out.clone()).clock(SharedClock::from_arc(clock.clone()));
+    let buffer = DefaultScreenBuffer::with_drawer((4, 2), drawer);
+    let renderer = Renderer::<_, Buffered>::create_renderer_with_buffer((4, 2), buffer);
+    let mut r = start_renderer(renderer);
+
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let rect = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('"')))
+                .rect(Rect::from_coords(0, 0, 0, 0))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    // events are timestamped on the drawer thread, so it has to catch up before time moves on
+    r.snapshot()?;
+
+    clock.advance(Duration::from_millis(500));
+    r.move_drawable_to(rect, Point { x: 2, y: 1 })?;
+    r.render_frame()?;
+    r.snapshot()?;
+
+    let text = out.take();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", text);
+    assert!(lines[0].starts_with("{\"version\": 2, \"width\": 4, \"height\": 2"));
+    assert!(
+        lines[1].starts_with("[0.000000, \"o\", \"\\u001b[1;1H"),
+        "{}",
+        lines[1]
+    );
+    assert!(
+        lines[1].contains("\\\""),
+        "quotes have to be escaped: {}",
+        lines[1]
+    );
+    assert!(lines[2].starts_with("[0.500000, \"o\", \""), "{}", lines[2]);
+    assert!(lines[2].contains("\\u001b[2;3H"), "{}", lines[2]);
+    Ok(())
+}
+
+#[test]
+fn records_with_the_options_of_the_terminal_drawer() {
+    let out = SharedWriter::default();
+    let options = CrosstermOptions::default()
+        .color_mode(ColorMode::Monochrome)
+        .inline(InlineRegion {
+            origin: 3,
+            height: 2,
+            terminal_rows: 8,
+        });
+    let mut drawer = AsciicastCellDrawer::new(out.clone()).options(options);
+    drawer.set_string(
+        BatchDrawInfo {
+            start_x: 0,
+            y: 0,
+            segments: vec![BatchSegment {
+                text: String::from("a"),
+                fg_color: Some(Color::rgb(255, 0, 0)),
+                bg_color: None,
+                attrs: CellAttributes::NONE,
+            }],
+        },
+        (4, 2),
+    );
+    drawer.flush().unwrap();
+
+    let text = out.take();
+    let header = text.lines().next().expect("a header");
+    assert!(
+        header.contains("\"env\": {\"TERM\": \"xterm-mono\"}"),
+        "{}",
+        header
+    );
+    let event = text.lines().nth(1).expect("one output event");
+    // rows are moved into the inline region and no colors get written
+    assert!(event.contains("\\u001b[4;1H"), "{}", event);
+    assert!(!event.contains("38;"), "{}", event);
+}
+
+#[test]
+fn drawers_without_a_writer_fail_every_flush() {
+    let mut drawer = AsciicastCellDrawer::init();
+    drawer.set_string(
+        BatchDrawInfo {
+            start_x: 0,
+            y: 0,
+            segments: Vec::new(),
+        },
+        (4, 2),
+    );
+    for _ in 0..2 {
+        let err = drawer.flush().unwrap_err();
+        assert!(err.to_string().contains("nowhere to record to"), "{}", err);
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::render_batch::BatchOutcome;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Instant;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{BatchDrawInfo, DefaultScreenBuffer};
+use ltrender::{DrawObject, DrawObjectBuilder, Renderer};
+
+/// drawer, that counts its flushes
+#[derive(Debug, Default, Clone)]
+struct CountingDrawer {
+    flushes: Arc<AtomicUsize>,
+}
+
+impl CellDrawer for CountingDrawer {
+    fn init() -> Self {
+        Self::default()
+    }
+
+    fn set_string(&mut self, _batch: BatchDrawInfo, _size: (u16, u16)) {}
+
+    fn flush(&mut self) -> Result<(), DrawError> {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn rect_obj(chr: char, x: i32) -> Result<DrawObject, AppError> {
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.fill_style(TerminalChar::from_char(chr))
+                .border_style(
+                    ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle::AllRound(
+                        TerminalChar::from_char(chr),
+                    ),
+                )
+                .rect(Rect::from_coords(x, 0, x + 1, 1))
+        })?
+        .build()
+}
+
+#[test]
+fn batch_returns_per_operation_results() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Instant>::create_renderer((10, 2));
+    let r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let a = r.register_drawable(screen, rect_obj('a', 0)?)?;
+    r.destroy_drawable(a)?;
+
+    let b = rect_obj('b', 0)?;
+    let c = rect_obj('c', 4)?;
+    let results = r.batch(|batch| {
+        batch
+            .register_drawable(screen, b)
+            .register_drawable(screen, c)
+            .move_drawable_by(a, 1, 0);
+    })?;
+
+    assert_eq!(results.len(), 3);
+    let Ok(BatchOutcome::Registered(b_key)) = results[0] else {
+        panic!("expected the first object to be registered");
+    };
+    assert!(matches!(results[1], Ok(BatchOutcome::Registered(_))));
+    assert!(matches!(
+        results[2],
+        Err(AppError::Draw(DrawError::StaleDrawableHandle { .. }))
+    ));
+
+    let results = r.batch(|batch| {
+        batch.move_drawable_by(b_key, 1, 0);
+    })?;
+    assert!(matches!(results[0], Ok(BatchOutcome::Applied)));
+
+    let data = r.snapshot()?.expect("drawer should have flushed");
+    let row: String = data.content[..10]
+        .iter()
+        .map(|c| c.map(|c| c.chr).unwrap_or(' '))
+        .collect();
+    assert_eq!(row, " bb cc    ");
+    Ok(())
+}
+
+#[test]
+fn batch_flushes_only_once() -> Result<(), AppError> {
+    let drawer = CountingDrawer::default();
+    let buffer = DefaultScreenBuffer::with_drawer((10, 2), drawer.clone());
+    let r = start_renderer(Renderer::<_, Instant>::create_renderer_with_buffer(
+        (10, 2),
+        buffer,
+    ));
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let a = r.register_drawable(screen, rect_obj('a', 0)?)?;
+    r.snapshot()?;
+    let before = drawer.flushes.load(Ordering::SeqCst);
+
+    // outside of a batch, each of these would flush on its own
+    let b = rect_obj('b', 4)?;
+    let results = r.batch(|batch| {
+        batch
+            .register_drawable(screen, b)
+            .move_drawable_by(a, 1, 0)
+            .move_drawable_by(a, 1, 0)
+            .explicit_remove_drawable(a);
+    })?;
+    assert!(results.iter().all(Result::is_ok));
+    r.snapshot()?;
+    assert_eq!(drawer.flushes.load(Ordering::SeqCst), before + 1);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, Renderer};
+
+fn renderer_with_char(chr: char) -> Result<RenderHandle<Buffered>, AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((3, 1));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char(chr)))
+                .rect(Rect::from_coords(0, 0, 2, 0))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    Ok(r)
+}
+
+fn row(r: &RenderHandle<Buffered>) -> Result<String, AppError> {
+    let data = r.snapshot()?.expect("drawer should have flushed");
+    Ok(data
+        .content
+        .iter()
+        .map(|c| c.map(|c| c.chr).unwrap_or(' '))
+        .collect())
+}
+
+#[test]
+fn renderers_capture_independently() -> Result<(), AppError> {
+    let a = renderer_with_char('a')?;
+    let b = renderer_with_char('b')?;
+    assert_eq!(a.snapshot()?, None);
+
+    a.render_frame()?;
+    b.render_frame()?;
+    assert_eq!(row(&a)?, "aaa");
+    assert_eq!(row(&b)?, "bbb");
+
+    // every flush is kept in the history
+    a.render_frame()?;
+    let history = a.frame_history()?;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].content, history[1].content);
+    assert_eq!(b.frame_history()?.len(), 1);
+    Ok(())
+}
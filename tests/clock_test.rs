@@ -0,0 +1,71 @@
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::{Clock, DrawObjectBuilder, ManualClock, Renderer, ScaledClock, SharedClock};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+type TestRenderer = Renderer<DefaultScreenBuffer<TestCellDrawer>, Buffered>;
+
+fn drawn_chars(renderer: &TestRenderer) -> usize {
+    let data = renderer
+        .snapshot()
+        .unwrap()
+        .expect("drawer should have flushed");
+    data.content.iter().filter(|c| c.is_some()).count()
+}
+
+#[test]
+fn lifetimes_follow_the_renderers_clock() -> Result<(), AppError> {
+    let clock = Arc::new(ManualClock::default());
+    let mut renderer = TestRenderer::create_renderer((4, 1));
+    renderer.set_clock(SharedClock::from_arc(clock.clone()));
+    let screen = renderer.create_screen(AreaRect::FullScreen, 0);
+    let obj = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ForTime(Duration::from_secs(1)))
+        .layer(0)
+        .rect_drawable(|r| {
+            r.fill_style(TerminalChar::from_char('#'))
+                .rect(Rect::from_coords(0, 0, 1, 0))
+        })?
+        .build()?;
+    renderer.register_drawable(screen, obj)?;
+    renderer.render_frame()?;
+    assert_eq!(drawn_chars(&renderer), 2);
+
+    clock.advance(Duration::from_millis(900));
+    renderer.check_if_object_lifetime_ended()?;
+    renderer.render_frame()?;
+    assert_eq!(drawn_chars(&renderer), 2);
+
+    clock.advance(Duration::from_millis(200));
+    renderer.check_if_object_lifetime_ended()?;
+    renderer.render_frame()?;
+    assert_eq!(drawn_chars(&renderer), 0);
+    Ok(())
+}
+
+#[test]
+fn scaled_clock_pauses_and_scales() {
+    let inner = Arc::new(ManualClock::default());
+    let scaled = ScaledClock::new(SharedClock::from_arc(inner.clone()), 2.0);
+    let start = scaled.now();
+
+    inner.advance(Duration::from_secs(1));
+    assert_eq!(scaled.now() - start, Duration::from_secs(2));
+
+    scaled.pause();
+    inner.advance(Duration::from_secs(5));
+    assert_eq!(scaled.now() - start, Duration::from_secs(2));
+
+    scaled.set_scale(0.5);
+    scaled.resume();
+    inner.advance(Duration::from_secs(2));
+    assert_eq!(scaled.now() - start, Duration::from_secs(3));
+}
@@ -0,0 +1,91 @@
+use ascii_assets::Color;
+use ltrender::CellAttributes;
+use ltrender::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::color_mode::{
+    nearest_ansi16, nearest_ansi256,
+};
+use ltrender::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::crossterm_celldrawer::encode_batch;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    BatchDrawInfo, BatchSegment, ColorMode, CrosstermOptions,
+};
+
+fn batch(text: &str, fg: Option<Color>, bg: Option<Color>) -> BatchDrawInfo {
+    BatchDrawInfo {
+        start_x: 0,
+        y: 0,
+        segments: vec![BatchSegment {
+            text: text.to_string(),
+            fg_color: fg,
+            bg_color: bg,
+            attrs: CellAttributes::NONE,
+        }],
+    }
+}
+
+#[test]
+fn detects_mode_from_env_values() {
+    let detect = ColorMode::from_env_values;
+    assert_eq!(
+        detect(Some("truecolor"), Some("xterm-256color")),
+        ColorMode::TrueColor
+    );
+    assert_eq!(detect(None, Some("tmux-256color")), ColorMode::Ansi256);
+    assert_eq!(detect(None, Some("linux")), ColorMode::Ansi16);
+    assert_eq!(detect(None, Some("xterm-direct")), ColorMode::TrueColor);
+    assert_eq!(detect(None, Some("dumb")), ColorMode::Monochrome);
+    assert_eq!(detect(None, None), ColorMode::TrueColor);
+}
+
+#[test]
+fn maps_to_nearest_palette_color() {
+    assert_eq!(nearest_ansi256((255, 0, 0)), 196);
+    assert_eq!(nearest_ansi256((0, 0, 0)), 16);
+    assert_eq!(nearest_ansi256((128, 128, 128)), 244);
+    assert_eq!(nearest_ansi16((250, 10, 10)), 9);
+    assert_eq!(nearest_ansi16((0, 0, 120)), 4);
+}
+
+#[test]
+fn encodes_colors_per_mode() {
+    let b = batch(
+        "ab",
+        Some(Color::rgb(255, 0, 0)),
+        Some(Color::rgb(0, 0, 128)),
+    );
+    let encode = |mode| encode_batch(&b, &CrosstermOptions::default().color_mode(mode));
+
+    assert_eq!(
+        encode(ColorMode::TrueColor),
+        "\x1b[1;1H\x1b[38;2;255;0;0m\x1b[48;2;0;0;128mab\x1b[0m"
+    );
+    assert_eq!(
+        encode(ColorMode::Ansi256),
+        "\x1b[1;1H\x1b[38;5;196m\x1b[48;5;18mab\x1b[0m"
+    );
+    assert_eq!(
+        encode(ColorMode::Ansi16),
+        "\x1b[1;1H\x1b[91m\x1b[44mab\x1b[0m"
+    );
+    assert_eq!(
+        encode(ColorMode::Monochrome),
+        "\x1b[1;1H\x1b[39m\x1b[49mab\x1b[0m"
+    );
+}
+
+#[test]
+fn dithering_mixes_palette_colors() {
+    // halfway between two of the 16 colors, so neighbouring cells have to round differently
+    let b = batch("xxxx", Some(Color::rgb(192, 0, 0)), None);
+    let options = CrosstermOptions::default()
+        .color_mode(ColorMode::Ansi16)
+        .dither(true);
+    let out = encode_batch(&b, &options);
+    assert!(out.contains("\x1b[31m"), "{:?}", out);
+    assert!(out.contains("\x1b[91m"), "{:?}", out);
+
+    // without dithering the whole segment gets a single color
+    let out = encode_batch(&b, &options.dither(false));
+    assert_eq!(
+        out.matches("\x1b[3").count() + out.matches("\x1b[9").count(),
+        1
+    );
+}
@@ -0,0 +1,216 @@
+//! fixtures shared by the integration tests, not every test uses all of them
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use ascii_assets::TerminalChar;
+use crossbeam_channel::Sender;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ltrender::drawable_traits::basic_draw_creator::BasicDrawCreator;
+use ltrender::error::DrawError;
+use ltrender::input_handler::manager::{
+    EventHandler, EventManagerCommand, EventManagerState, KeyMessage, MouseState,
+    SubscriptionMessage, TargetScreen,
+};
+use ltrender::terminal_buffer::buffer_and_celldrawer::screen_buffer::CellDrawerCommand;
+use ltrender::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::test_celldrawer::FrameCapture;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    BatchDrawInfo, DefaultScreenBuffer, TestCellDrawer,
+};
+use ltrender::terminal_buffer::{
+    CellDrawer, LayeredCells, ScreenBuffer, ScreenBufferCore, StyledChar,
+};
+use ltrender::{Drawable, SpriteRegistry, UpdateIntervalHandler};
+
+/// writer, whose output stays readable after being handed to a drawer
+#[derive(Clone, Default)]
+pub struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+impl SharedWriter {
+    /// everything written since the last call
+    pub fn take(&self) -> String {
+        String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+    }
+}
+
+impl Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// drawer, whose flushes always fail
+#[derive(Debug, Default)]
+pub struct BrokenDrawer {
+    pub batches: Arc<AtomicUsize>,
+}
+
+impl CellDrawer for BrokenDrawer {
+    fn init() -> Self {
+        Self::default()
+    }
+
+    fn set_string(&mut self, _batch: BatchDrawInfo, _size: (u16, u16)) {
+        self.batches.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn flush(&mut self) -> Result<(), DrawError> {
+        Err(std::io::Error::other("terminal gone").into())
+    }
+}
+
+/// buffer, whose updates of the terminal fail, once it is told to
+#[derive(Debug)]
+pub struct FailingBuffer {
+    inner: DefaultScreenBuffer<TestCellDrawer>,
+    pub failing: Arc<AtomicBool>,
+}
+
+impl ScreenBufferCore for FailingBuffer {
+    fn cell_info_mut(&mut self) -> &mut LayeredCells {
+        self.inner.cell_info_mut()
+    }
+    fn cell_info(&self) -> &LayeredCells {
+        self.inner.cell_info()
+    }
+    fn front_buffer_mut(&mut self) -> &mut Vec<Option<StyledChar>> {
+        self.inner.front_buffer_mut()
+    }
+    fn front_buffer(&self) -> &Vec<Option<StyledChar>> {
+        self.inner.front_buffer()
+    }
+    fn intervals_mut(&mut self) -> &mut UpdateIntervalHandler {
+        self.inner.intervals_mut()
+    }
+    fn size(&self) -> (u16, u16) {
+        self.inner.size()
+    }
+}
+
+impl ScreenBuffer for FailingBuffer {
+    type Drawer = TestCellDrawer;
+    fn new(size: (u16, u16)) -> Self {
+        FailingBuffer {
+            inner: DefaultScreenBuffer::new(size),
+            failing: Arc::default(),
+        }
+    }
+    fn update_terminal(&mut self, expand: usize) -> Result<(), DrawError> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(std::io::Error::other("terminal gone").into());
+        }
+        self.inner.update_terminal(expand)
+    }
+    fn drawer_sender(&self) -> mpsc::SyncSender<CellDrawerCommand> {
+        self.inner.drawer_sender()
+    }
+    fn resize(&mut self, new_size: (u16, u16)) {
+        self.inner.resize(new_size)
+    }
+    fn capture(&self) -> Option<FrameCapture> {
+        self.inner.capture()
+    }
+}
+
+fn draw_single(chr: char) -> BasicDrawCreator {
+    let mut creator = BasicDrawCreator::new();
+    creator.draw_char((0, 0), TerminalChar::from_char(chr));
+    creator
+}
+
+/// drawable, that draws an 'r', but rejects every key and every selection of its screen
+#[derive(Debug)]
+pub struct RejectingDrawable;
+
+impl Drawable for RejectingDrawable {
+    fn draw(&mut self, _sprites: &SpriteRegistry) -> Result<BasicDrawCreator, DrawError> {
+        Ok(draw_single('r'))
+    }
+
+    fn size(&self, _sprites: &SpriteRegistry) -> Result<(u16, u16), DrawError> {
+        Ok((1, 1))
+    }
+
+    fn on_any_key_press(
+        &mut self,
+        _msg: KeyMessage,
+        _screen: TargetScreen,
+    ) -> Result<(), DrawError> {
+        Err(std::io::Error::other("key rejected").into())
+    }
+
+    fn on_screen_select(&mut self, _selected_screen: TargetScreen) -> Result<(), DrawError> {
+        Err(std::io::Error::other("selection rejected").into())
+    }
+}
+
+/// drawable, that draws a 'p', but panics on every key and every selection of its screen
+#[derive(Debug)]
+pub struct PanickingOnInput;
+
+impl Drawable for PanickingOnInput {
+    fn draw(&mut self, _sprites: &SpriteRegistry) -> Result<BasicDrawCreator, DrawError> {
+        Ok(draw_single('p'))
+    }
+
+    fn size(&self, _sprites: &SpriteRegistry) -> Result<(u16, u16), DrawError> {
+        Ok((1, 1))
+    }
+
+    fn on_any_key_press(
+        &mut self,
+        _msg: KeyMessage,
+        _screen: TargetScreen,
+    ) -> Result<(), DrawError> {
+        panic!("cannot handle keys");
+    }
+
+    fn on_screen_select(&mut self, _selected_screen: TargetScreen) -> Result<(), DrawError> {
+        panic!("cannot be selected");
+    }
+}
+
+/// Stands in for the crossterm event manager.
+/// Answers the subscriptions of the render thread and hands out their senders,
+/// so tests can inject messages
+pub fn fake_event_source() -> (EventHandler, Receiver<Sender<SubscriptionMessage>>) {
+    let state = Arc::new(Mutex::new(EventManagerState {
+        pressed_keys: HashMap::new(),
+        mouse_state: MouseState::default(),
+        terminal_size: (4, 1),
+        targeted_screen: TargetScreen::None,
+        is_terminal_focused: true,
+    }));
+    let (handler, commands) = EventHandler::new(state);
+    let (subscribers_tx, subscribers) = mpsc::channel();
+    thread::spawn(move || {
+        for (id, cmd) in commands.into_iter().enumerate() {
+            if let EventManagerCommand::Subscribe(_, tx) = cmd {
+                let _ = tx.send(SubscriptionMessage::SubscriptionId(id));
+                let _ = subscribers_tx.send(tx);
+            }
+        }
+    });
+    (handler, subscribers)
+}
+
+/// press a key through the fake event source, the key hook of the render thread subscribes first
+pub fn press_key(subscribers: &Receiver<Sender<SubscriptionMessage>>, key: char) {
+    let keys = subscribers
+        .recv()
+        .expect("render thread should subscribe to keys");
+    keys.send(SubscriptionMessage::Key {
+        msg: KeyMessage::Pressed(KeyCode::Char(key), KeyModifiers::NONE),
+        screen: TargetScreen::None,
+    })
+    .expect("key hook should be alive");
+}
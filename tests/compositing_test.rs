@@ -0,0 +1,121 @@
+use ascii_assets::{Color, TerminalChar};
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{DefaultScreenBuffer, TestCellDrawer};
+use ltrender::terminal_buffer::compositing::blend;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::terminal_buffer::standard_drawables::text_drawable::{
+    LineInfo, TextAlignment, TextDrawable, TextStyle,
+};
+use ltrender::{DrawObjectBuilder, Renderer, ScreenKey};
+
+const RED: (u8, u8, u8) = (200, 0, 0);
+
+fn setup() -> Result<(RenderHandle<Buffered>, ScreenKey), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 2));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let fill = TerminalChar {
+        chr: 'x',
+        fg_color: Some(Color::rgb(0, 0, 0)),
+        bg_color: Some(Color::rgb(RED.0, RED.1, RED.2)),
+    };
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(fill))
+                .fill_style(fill)
+                .rect(Rect::from_coords(0, 0, 4, 2))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    Ok((r, screen))
+}
+
+fn label(text: &str, foreground: Option<Color>) -> TextDrawable {
+    TextDrawable {
+        area: Rect::from_coords(0, 0, 4, 1),
+        lines: vec![LineInfo {
+            text: text.to_string(),
+            spans: Vec::new(),
+            alignment: TextAlignment::Left,
+            default_style: TextStyle {
+                foreground,
+                ..Default::default()
+            },
+        }],
+        wrapping: false,
+        scroll_y: 0,
+    }
+}
+
+#[test]
+fn labels_inherit_the_background_below() -> Result<(), AppError> {
+    let (mut r, screen) = setup()?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(1)
+        .drawable(Box::new(label("h i", Some(Color::rgb(255, 255, 255)))))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+
+    let h = frame.content[0].expect("label should be drawn");
+    assert_eq!(h.chr, 'h');
+    assert_eq!(h.fg_color, Some(Color::rgb(255, 255, 255)));
+    assert_eq!(h.bg_color, Some(Color::rgb(RED.0, RED.1, RED.2)));
+
+    // the space of the label is transparent
+    let gap = frame.content[1].expect("fill should shine through");
+    assert_eq!(gap.chr, 'x');
+    assert_eq!(gap.fg_color, Some(Color::rgb(0, 0, 0)));
+    Ok(())
+}
+
+#[test]
+fn translucent_objects_blend_with_the_layers_below() -> Result<(), AppError> {
+    let (mut r, screen) = setup()?;
+    let tint = TerminalChar {
+        chr: ' ',
+        fg_color: None,
+        bg_color: Some(Color::rgb(0, 0, 200)),
+    };
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(1)
+        .alpha(128)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(tint))
+                .fill_style(tint)
+                .rect(Rect::from_coords(0, 0, 4, 2))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+
+    let cell = frame.content[0].expect("cell should be drawn");
+    assert_eq!(cell.chr, 'x');
+    assert_eq!(cell.bg_color, Some(Color::rgb(99, 0, 100)));
+    assert_eq!(cell.fg_color, Some(Color::rgb(0, 0, 100)));
+    Ok(())
+}
+
+#[test]
+fn blends_only_rgb_colors() {
+    let red = Color::rgb(255, 0, 0);
+    let blue = Color::rgb(0, 0, 255);
+    assert_eq!(blend(red, Some(blue), 255), red);
+    assert_eq!(blend(red, Some(blue), 0), blue);
+    assert_eq!(blend(red, Some(blue), 128), Color::rgb(128, 0, 127));
+    assert_eq!(blend(red, Some(Color::reset()), 128), red);
+    assert_eq!(blend(red, None, 128), red);
+}
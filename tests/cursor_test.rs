@@ -0,0 +1,76 @@
+use ltrender::display_screen::AreaRect;
+use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CursorState;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{DefaultScreenBuffer, TestCellDrawer};
+use ltrender::{CursorShape, Renderer};
+
+#[test]
+fn cursor_follows_its_screen() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((10, 5));
+    let r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FromPoints((2, 1).into(), (8, 4).into()), 0)?;
+
+    r.set_cursor_position(screen, (3, 1))?;
+    r.set_cursor_shape(CursorShape::Bar, true)?;
+    r.show_cursor()?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(
+        frame.cursor,
+        CursorState {
+            visible: true,
+            position: (5, 2),
+            shape: CursorShape::Bar,
+            blinking: true,
+        }
+    );
+
+    r.change_screen_area(screen, AreaRect::FromPoints((0, 0).into(), (6, 3).into()))?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.cursor.position, (3, 1));
+
+    r.hide_cursor()?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert!(!frame.cursor.visible);
+    Ok(())
+}
+
+#[test]
+fn cursor_outside_of_its_screen_is_hidden() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((10, 5));
+    let r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FromPoints((0, 0).into(), (4, 2).into()), 0)?;
+    r.set_cursor_position(screen, (4, 0))?;
+    r.show_cursor()?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert!(!frame.cursor.visible);
+
+    r.destroy_screen(screen)?;
+    let err = r.set_cursor_position(screen, (0, 0)).unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::Draw(DrawError::StaleScreenKey(key)) if key == screen
+    ));
+    Ok(())
+}
+
+#[test]
+fn encodes_decscusr_and_position() {
+    let cursor = CursorState {
+        visible: true,
+        position: (4, 2),
+        shape: CursorShape::Underline,
+        blinking: false,
+    };
+    assert_eq!(cursor.escape_sequence(), "\x1b[4 q\x1b[3;5H\x1b[?25h");
+    assert_eq!(CursorShape::Block.decscusr_param(true), 1);
+    assert_eq!(CursorShape::Default.decscusr_param(false), 0);
+    assert_eq!(CursorState::default().escape_sequence(), "\x1b[?25l");
+}
@@ -0,0 +1,58 @@
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, Renderer};
+
+const SIZE: (u16, u16) = (20, 5);
+
+#[test]
+fn unchanged_cells_are_not_redrawn() -> Result<(), AppError> {
+    let renderer = Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer(SIZE);
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+
+    let rect = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('#')))
+                .rect(Rect::from_coords(0, 0, 9, 2))
+                .fill_style(TerminalChar::from_char('.'))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+
+    r.render_frame()?;
+    let first = r.snapshot()?.expect("drawer should have flushed");
+    // nothing has been drawn before, so every cell gets written once
+    assert_eq!(
+        first.amount_of_changed_chars,
+        SIZE.0 as usize * SIZE.1 as usize
+    );
+
+    // rendering the same content again must not write anything
+    r.render_frame()?;
+    let second = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(
+        second.amount_of_changed_chars,
+        first.amount_of_changed_chars
+    );
+
+    // moving the rect by one cell only touches its left and right edges
+    r.move_drawable_by(rect, 1, 0)?;
+    r.render_frame()?;
+    let third = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(
+        third.amount_of_changed_chars - second.amount_of_changed_chars,
+        8
+    );
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, DrawError, ErrorPolicy, RenderError, RenderPhase};
+use ltrender::input_handler::manager::TargetScreen;
+use ltrender::input_handler::screen_select_handler::{ScreenSelectHMsg, ScreenSelectHandler};
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::{start_renderer, start_renderer_with_event_handler};
+use ltrender::rendering::renderer::{Buffered, Instant};
+use ltrender::terminal_buffer::ScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{DefaultScreenBuffer, TestCellDrawer};
+use ltrender::{DrawObjectBuilder, ManualClock, Renderer, SharedClock};
+
+use common::{BrokenDrawer, FailingBuffer, RejectingDrawable, fake_event_source, press_key};
+
+fn start_broken() -> RenderHandle<Buffered> {
+    start_renderer(Renderer::<DefaultScreenBuffer<BrokenDrawer>, Buffered>::create_renderer((4, 1)))
+}
+
+#[test]
+fn drawer_errors_reach_the_subscriber() -> Result<(), AppError> {
+    let r = start_broken();
+    let errors = r.errors()?;
+    r.create_screen(AreaRect::FullScreen, 0)?;
+    r.render_frame()?;
+
+    let err = errors
+        .recv_timeout(Duration::from_secs(1))
+        .expect("flush error should be reported");
+    assert_eq!(err.phase, RenderPhase::Drawer);
+    assert!(matches!(err.error, AppError::Draw(DrawError::Io(_))));
+    assert_eq!(err.to_string(), "error while drawing: terminal gone");
+
+    // the renderer keeps running by default
+    r.create_screen(AreaRect::FullScreen, 1)?;
+    Ok(())
+}
+
+#[test]
+fn stop_policy_ends_the_render_thread() -> Result<(), AppError> {
+    let r = start_broken();
+    let errors = r.errors()?;
+    r.set_error_policy(ErrorPolicy::Stop)?;
+    r.render_frame()?;
+
+    errors
+        .recv_timeout(Duration::from_secs(1))
+        .expect("flush error should be reported");
+    r.shutdown()?;
+    assert!(matches!(
+        r.create_screen(AreaRect::FullScreen, 0),
+        Err(AppError::SendError)
+    ));
+    Ok(())
+}
+
+#[test]
+fn screen_select_callbacks_report_missing_screens() -> Result<(), AppError> {
+    let r = start_renderer(
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1)),
+    );
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    r.add_on_screen_select(screen, || {})?;
+
+    r.destroy_screen(screen)?;
+    let err = r.add_on_screen_select(screen, || {}).unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::Draw(DrawError::StaleScreenKey(key)) if key == screen
+    ));
+    Ok(())
+}
+
+/// instant renderer, whose refreshes fail once the returned flag is set
+fn start_failing(clock: SharedClock) -> (RenderHandle<Instant>, Arc<AtomicBool>) {
+    let buffer = FailingBuffer::new((4, 1));
+    let failing = buffer.failing.clone();
+    let mut renderer = Renderer::<_, Instant>::create_renderer_with_buffer((4, 1), buffer);
+    renderer.set_clock(clock);
+    (start_renderer(renderer), failing)
+}
+
+fn register_rejecting(
+    r: &mut RenderHandle<Instant>,
+    lifetime: ObjectLifetime,
+) -> Result<(), AppError> {
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(lifetime)
+        .layer(0)
+        .drawable(Box::new(RejectingDrawable))
+        .screen(screen)
+        .build_and_register(r)?;
+    Ok(())
+}
+
+fn next_error(errors: &Receiver<RenderError>) -> RenderError {
+    errors
+        .recv_timeout(Duration::from_secs(1))
+        .expect("error should be reported")
+}
+
+#[test]
+fn frame_based_cleanup_errors_reach_the_subscriber() -> Result<(), AppError> {
+    let (mut r, failing) = start_failing(SharedClock::default());
+    let errors = r.errors()?;
+    failing.store(true, Ordering::SeqCst);
+    // the object stays registered, even though refreshing after it fails
+    assert!(register_rejecting(&mut r, ObjectLifetime::RemoveNextFrame).is_err());
+
+    let err = next_error(&errors);
+    assert_eq!(err.phase, RenderPhase::FramebasedCleanup);
+    assert_eq!(
+        err.to_string(),
+        "error while removing frame based objects: terminal gone"
+    );
+    Ok(())
+}
+
+#[test]
+fn lifetime_check_errors_reach_the_subscriber() -> Result<(), AppError> {
+    let clock = Arc::new(ManualClock::default());
+    let (mut r, failing) = start_failing(SharedClock::from_arc(clock.clone()));
+    let errors = r.errors()?;
+    register_rejecting(&mut r, ObjectLifetime::ForTime(Duration::from_secs(1)))?;
+
+    failing.store(true, Ordering::SeqCst);
+    clock.advance(Duration::from_secs(2));
+    let err = next_error(&errors);
+    assert_eq!(err.phase, RenderPhase::LifetimeCheck);
+    assert!(matches!(err.error, AppError::Draw(DrawError::Io(_))));
+    Ok(())
+}
+
+#[test]
+fn input_errors_reach_the_subscriber() -> Result<(), AppError> {
+    let (handler, subscribers) = fake_event_source();
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1));
+    let mut r = start_renderer_with_event_handler(renderer, &handler);
+    let errors = r.errors()?;
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(Box::new(RejectingDrawable))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+
+    press_key(&subscribers, 'a');
+
+    let err = next_error(&errors);
+    assert_eq!(err.phase, RenderPhase::Input);
+    assert_eq!(err.to_string(), "error while handling input: key rejected");
+    Ok(())
+}
+
+#[test]
+fn screen_selection_errors_reach_the_subscriber() -> Result<(), AppError> {
+    let (handler, _subscribers) = fake_event_source();
+    let (selections, select_rx) = mpsc::channel();
+    let (approvals, _approved) = mpsc::channel();
+    let mut renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1));
+    renderer.add_screen_select_handler(ScreenSelectHandler::new(select_rx, approvals));
+    let mut r = start_renderer_with_event_handler(renderer, &handler);
+    let errors = r.errors()?;
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(Box::new(RejectingDrawable))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    // selections only count, while the mouse is above a drawn object
+    r.render_frame()?;
+
+    selections
+        .send(ScreenSelectHMsg::Selection(TargetScreen::Screen(screen)))
+        .expect("renderer should hold the screen select handler");
+    let err = next_error(&errors);
+    assert_eq!(err.phase, RenderPhase::ScreenSelection);
+    assert_eq!(
+        err.to_string(),
+        "error while selecting a screen: selection rejected"
+    );
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use ascii_assets::{Color, TerminalChar};
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::terminal_buffer::standard_drawables::text_drawable::{
+    LineInfo, TextAlignment, TextDrawable, TextStyle,
+};
+use ltrender::{DrawObjectBuilder, ExportOptions, Renderer, export_html, export_svg};
+
+fn red_box() -> Result<RenderHandle<Buffered>, AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 2));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let border = TerminalChar {
+        chr: '<',
+        fg_color: Some(Color::rgb(255, 0, 0)),
+        bg_color: Some(Color::rgb(0, 0, 255)),
+    };
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(border))
+                .rect(Rect::from_coords(0, 0, 1, 0))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    Ok(r)
+}
+
+#[test]
+fn composed_content_matches_the_flushed_frame() -> Result<(), AppError> {
+    let r = red_box()?;
+    let composed = r.composed_content()?;
+    let flushed = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(composed.size, (4, 2));
+    assert_eq!(composed.content, flushed.content);
+    Ok(())
+}
+
+#[test]
+fn html_groups_runs_of_equal_colors() -> Result<(), AppError> {
+    let r = red_box()?;
+    let html = export_html(
+        &r.composed_content()?,
+        &ExportOptions::default().title("a & b"),
+    );
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>a &amp; b</title>"));
+    assert!(html.contains(
+        "<span style=\"color:#ff0000;background-color:#0000ff;\">&lt;&lt;</span>  \n    </pre>"
+    ));
+    Ok(())
+}
+
+#[test]
+fn svg_contains_backgrounds_and_text() -> Result<(), AppError> {
+    let r = red_box()?;
+    let svg = export_svg(
+        &r.composed_content()?,
+        &ExportOptions::default().font_size(10),
+    );
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"24\"")
+    );
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"12\" height=\"12\" fill=\"#0000ff\"/>"));
+    assert!(svg.contains("fill=\"#ff0000\" textLength=\"12\""));
+    assert!(svg.contains(">&lt;&lt;</text>"));
+    assert_eq!(svg.matches("<text").count(), 1);
+    assert!(svg.ends_with("</svg>\n"));
+    Ok(())
+}
+
+#[test]
+fn exporters_keep_grapheme_clusters() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let text = TextDrawable {
+        area: Rect::from_coords(0, 0, 4, 1),
+        lines: vec![LineInfo {
+            text: String::from("e\u{301}中x"),
+            spans: Vec::new(),
+            alignment: TextAlignment::Left,
+            default_style: TextStyle::default(),
+        }],
+        wrapping: false,
+        scroll_y: 0,
+    };
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(Box::new(text))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+
+    let composed = r.composed_content()?;
+    let flushed = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(composed.clusters, flushed.clusters);
+    assert_eq!(
+        composed.clusters[0].map(|c| c.to_string()),
+        Some(String::from("e\u{301}"))
+    );
+
+    let options = ExportOptions::default();
+    assert!(export_html(&composed, &options).contains(">e\u{301}中x</pre>"));
+    assert!(export_svg(&composed, &options).contains(">e\u{301}中x</text>"));
+    Ok(())
+}
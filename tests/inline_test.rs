@@ -0,0 +1,119 @@
+mod common;
+
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::crossterm_celldrawer::{
+    BEGIN_SYNCHRONIZED_UPDATE, END_SYNCHRONIZED_UPDATE,
+};
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    BatchDrawInfo, CrosstermCellDrawer, CrosstermOptions, DefaultScreenBuffer,
+};
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, InlineRegion, Renderer};
+
+use common::SharedWriter;
+
+const REGION: InlineRegion = InlineRegion {
+    origin: 5,
+    height: 2,
+    terminal_rows: 8,
+};
+
+#[test]
+fn draws_relative_to_the_region() -> Result<(), AppError> {
+    let out = SharedWriter::default();
+    let options = CrosstermOptions::default()
+        .inline(REGION)
+        .synchronized_output(false);
+    let drawer = CrosstermCellDrawer::with_writer(out.clone(), options);
+    let buffer = DefaultScreenBuffer::with_drawer((4, 2), drawer);
+    let renderer = Renderer::<_, Buffered>::create_renderer_with_buffer((4, 2), buffer);
+    let mut r = start_renderer(renderer);
+
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('#')))
+                .rect(Rect::from_coords(0, 0, 4, 2))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.set_cursor_position(screen, (1, 1))?;
+    r.show_cursor()?;
+    r.render_frame()?;
+    r.snapshot()?;
+    let frame = out.take();
+    assert!(frame.contains("\x1b[6;1H"));
+    assert!(frame.ends_with("\x1b[7;2H\x1b[?25h"));
+
+    // one line fits below the region, the others scroll the rows above it
+    r.print_above("a\nb\nc")?;
+    r.snapshot()?;
+    assert_eq!(
+        out.take(),
+        "\x1b[0m\x1b[6;1H\x1b[1La\r\n\x1b[1;6r\x1b[6;1H\r\n\x1b[2Kb\r\n\x1b[2Kc\x1b[r\x1b[0 q\x1b[8;2H\x1b[?25h"
+    );
+    Ok(())
+}
+
+#[test]
+fn leaves_the_last_frame_in_the_scrollback() {
+    let out = SharedWriter::default();
+    let options = CrosstermOptions::default()
+        .inline(REGION)
+        .synchronized_output(false);
+    let mut drawer = CrosstermCellDrawer::with_writer(out.clone(), options);
+    drawer.set_string(
+        BatchDrawInfo {
+            start_x: 0,
+            y: 1,
+            segments: Vec::new(),
+        },
+        (4, 2),
+    );
+    drawer.flush().unwrap();
+    assert!(out.take().starts_with("\x1b[7;1H"));
+
+    drop(drawer);
+    assert_eq!(out.take(), "\x1b[0m\x1b[7;1H\r\n");
+}
+
+#[test]
+fn printing_above_joins_an_open_synchronized_update() {
+    let out = SharedWriter::default();
+    let options = CrosstermOptions::default()
+        .inline(REGION)
+        .synchronized_output(true);
+    let mut drawer = CrosstermCellDrawer::with_writer(out.clone(), options);
+
+    // on its own, printing is an update of its own
+    drawer.print_above("a");
+    let printed = out.take();
+    assert!(printed.starts_with(BEGIN_SYNCHRONIZED_UPDATE));
+    assert!(printed.ends_with(END_SYNCHRONIZED_UPDATE));
+
+    // in the middle of a frame, the update opened by drawing gets ended by the flush
+    drawer.set_string(
+        BatchDrawInfo {
+            start_x: 0,
+            y: 1,
+            segments: Vec::new(),
+        },
+        (4, 2),
+    );
+    drawer.print_above("b");
+    drawer.flush().unwrap();
+    let frame = out.take();
+    assert!(frame.starts_with(BEGIN_SYNCHRONIZED_UPDATE));
+    assert_eq!(frame.matches(BEGIN_SYNCHRONIZED_UPDATE).count(), 1);
+    assert_eq!(frame.matches(END_SYNCHRONIZED_UPDATE).count(), 1);
+    assert!(frame.ends_with(END_SYNCHRONIZED_UPDATE));
+}
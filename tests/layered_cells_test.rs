@@ -0,0 +1,67 @@
+use ascii_assets::{Color, TerminalChar};
+use ltrender::ObjectId;
+use ltrender::terminal_buffer::{CellAttributes, CharacterInfo, LayeredCells};
+
+fn id(index: usize) -> ObjectId {
+    ObjectId {
+        index,
+        generation: 0,
+    }
+}
+
+fn info(obj: usize, layer: usize, chr: char) -> CharacterInfo {
+    CharacterInfo {
+        display_id: id(obj),
+        layer,
+        screen_layer: 0,
+        chr: TerminalChar {
+            chr,
+            fg_color: None,
+            bg_color: Some(Color::rgb(0, 0, 0)),
+        },
+        attrs: CellAttributes::NONE,
+        cluster: None,
+        alpha: u8::MAX,
+    }
+}
+
+#[test]
+fn cells_keep_their_objects_sorted_by_layer() {
+    let mut cells = LayeredCells::new(2);
+    cells.place(id(0), [(0, info(0, 2, 'a'))]);
+    cells.place(id(1), [(0, info(1, 0, 'b'))]);
+    cells.place(id(2), [(0, info(2, 1, 'c'))]);
+
+    let order: Vec<_> = cells[0].layers().iter().map(|i| i.chr.chr).collect();
+    assert_eq!(order, ['b', 'c', 'a']);
+    assert_eq!(
+        cells.top(0).map(|(obj, c)| (obj, c.chr.chr)),
+        Some((id(0), 'a'))
+    );
+
+    cells.remove(id(0));
+    assert_eq!(
+        cells.top(0).map(|(obj, c)| (obj, c.chr.chr)),
+        Some((id(2), 'c'))
+    );
+    assert!(cells.top(1).is_none());
+}
+
+#[test]
+fn moving_an_object_vacates_its_old_cells() {
+    let mut cells = LayeredCells::new(8);
+    let vacated = cells.place(id(0), (0..3).map(|idx| (idx, info(0, 0, 'x'))));
+    assert!(vacated.is_empty());
+    assert_eq!(cells.spans(id(0)), vec![0..3]);
+
+    // drawn in any order, the last char of a cell wins
+    let moved = [5, 2, 3, 2].map(|idx| (idx, info(0, 0, if idx == 2 { 'y' } else { 'x' })));
+    let vacated = cells.place(id(0), moved);
+    assert_eq!(vacated, vec![0..2]);
+    assert_eq!(cells.spans(id(0)), [2..4, 5..6]);
+    assert!(cells[0].is_empty());
+    assert_eq!(cells[2].get(id(0)).map(|i| i.chr.chr), Some('y'));
+
+    assert_eq!(cells.remove(id(0)), [2..4, 5..6]);
+    assert!(cells.iter().all(|cell| cell.is_empty()));
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{BatchDrawInfo, DefaultScreenBuffer};
+use ltrender::terminal_buffer::standard_drawables::text_drawable::{
+    LineInfo, TextAlignment, TextDrawable, TextStyle,
+};
+use ltrender::{DrawObjectBuilder, Renderer};
+
+/// drawer, that counts its flushes and notices being dropped
+#[derive(Debug, Default, Clone)]
+struct TrackingDrawer {
+    flushes: Arc<AtomicUsize>,
+    dropped: Arc<AtomicBool>,
+}
+
+impl CellDrawer for TrackingDrawer {
+    fn init() -> Self {
+        Self::default()
+    }
+
+    fn set_string(&mut self, _batch: BatchDrawInfo, _size: (u16, u16)) {}
+
+    fn flush(&mut self) -> Result<(), DrawError> {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl Drop for TrackingDrawer {
+    fn drop(&mut self) {
+        self.dropped.store(true, Ordering::SeqCst);
+    }
+}
+
+fn start(drawer: TrackingDrawer) -> RenderHandle<Buffered> {
+    let buffer = DefaultScreenBuffer::with_drawer((4, 1), drawer);
+    start_renderer(Renderer::<_, Buffered>::create_renderer_with_buffer(
+        (4, 1),
+        buffer,
+    ))
+}
+
+#[test]
+fn shutdown_joins_the_render_and_drawer_threads() -> Result<(), AppError> {
+    let drawer = TrackingDrawer::default();
+    let r = start(drawer.clone());
+    r.create_screen(AreaRect::FullScreen, 0)?;
+    r.render_frame()?;
+
+    r.shutdown()?;
+    assert!(drawer.dropped.load(Ordering::SeqCst));
+    assert!(drawer.flushes.load(Ordering::SeqCst) > 0);
+    // shutting down again is fine, but the renderer is gone
+    r.shutdown()?;
+    assert!(matches!(r.render_frame(), Err(AppError::SendError)));
+    Ok(())
+}
+
+#[test]
+fn dropping_the_last_handle_shuts_down() -> Result<(), AppError> {
+    let drawer = TrackingDrawer::default();
+    let r = start(drawer.clone());
+    let other = r.clone();
+    drop(r);
+    other.create_screen(AreaRect::FullScreen, 0)?;
+    assert!(!drawer.dropped.load(Ordering::SeqCst));
+
+    drop(other);
+    assert!(drawer.dropped.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[test]
+fn handles_kept_by_callbacks_can_be_weak() -> Result<(), AppError> {
+    let drawer = TrackingDrawer::default();
+    let r = start(drawer.clone());
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let callback_handle = r.downgrade();
+    r.add_on_screen_select(screen, move || {
+        if let Some(r) = callback_handle.upgrade() {
+            let _ = r.render_frame();
+        }
+    })?;
+    let weak = r.downgrade();
+    assert!(weak.upgrade().is_some());
+
+    drop(r);
+    assert!(drawer.dropped.load(Ordering::SeqCst));
+    assert!(weak.upgrade().is_none());
+    Ok(())
+}
+
+#[test]
+fn panics_of_the_render_thread_are_reported() -> Result<(), AppError> {
+    let mut r = start(TrackingDrawer::default());
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let key = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(Box::new(TextDrawable {
+            area: Rect::from_coords(0, 0, 4, 1),
+            lines: vec![LineInfo {
+                text: String::from("a"),
+                spans: Vec::new(),
+                alignment: TextAlignment::Left,
+                default_style: TextStyle::default(),
+            }],
+            wrapping: false,
+            scroll_y: 0,
+        }))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+
+    let res = r.update::<TextDrawable, _>(key, |_| panic!("broken update"));
+    assert!(matches!(res, Err(AppError::RecvError)));
+    let err = r.shutdown().unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::RenderThreadPanicked(msg) if msg == "broken update"
+    ));
+    Ok(())
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::drawable_traits::basic_draw_creator::BasicDrawCreator;
+use ltrender::error::{AppError, DrawError, RenderError, RenderPhase};
+use ltrender::input_handler::manager::TargetScreen;
+use ltrender::input_handler::screen_select_handler::{ScreenSelectHMsg, ScreenSelectHandler};
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::{start_renderer, start_renderer_with_event_handler};
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{DefaultScreenBuffer, TestCellDrawer};
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, Drawable, Renderer, ScreenKey, SpriteRegistry};
+
+use common::{PanickingOnInput, fake_event_source, press_key};
+
+/// drawable, that panics while being drawn
+#[derive(Debug)]
+struct PanickingDrawable;
+
+impl Drawable for PanickingDrawable {
+    fn draw(&mut self, _sprites: &SpriteRegistry) -> Result<BasicDrawCreator, DrawError> {
+        panic!("cannot draw this");
+    }
+
+    fn size(&self, _sprites: &SpriteRegistry) -> Result<(u16, u16), DrawError> {
+        Ok((1, 1))
+    }
+}
+
+/// drawable, that draws a single char
+#[derive(Debug)]
+struct CharDrawable(char);
+
+impl Drawable for CharDrawable {
+    fn draw(&mut self, _sprites: &SpriteRegistry) -> Result<BasicDrawCreator, DrawError> {
+        let mut creator = BasicDrawCreator::new();
+        creator.draw_char((0, 0), TerminalChar::from_char(self.0));
+        Ok(creator)
+    }
+
+    fn size(&self, _sprites: &SpriteRegistry) -> Result<(u16, u16), DrawError> {
+        Ok((1, 1))
+    }
+}
+
+#[test]
+fn panicking_objects_get_quarantined() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1));
+    let mut r = start_renderer(renderer);
+    let errors = r.errors()?;
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('#')))
+                .rect(Rect::from_coords(0, 0, 4, 1))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    let broken = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(1)
+        .drawable(Box::new(PanickingDrawable))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+
+    // everything else keeps being rendered
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.content[0].map(|c| c.chr), Some('#'));
+
+    let err = errors
+        .recv_timeout(Duration::from_secs(1))
+        .expect("panic should be reported");
+    assert_eq!(err.phase, RenderPhase::Quarantine);
+    assert!(matches!(
+        err.error,
+        AppError::Draw(DrawError::DrawablePanicked { screen_id, obj_id, ref message })
+            if screen_id == screen && obj_id == broken.object_id && message == "cannot draw this"
+    ));
+    // quarantined objects are skipped from now on
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.content[0].map(|c| c.chr), Some('#'));
+    assert!(errors.try_recv().is_err());
+
+    // a replaced drawable leaves the quarantine
+    r.replace_drawable(broken, Box::new(CharDrawable('x')))?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.content[0].map(|c| c.chr), Some('x'));
+    assert!(errors.try_recv().is_err());
+    Ok(())
+}
+
+/// register the drawable on a new screen and draw it once
+fn add_drawn(
+    r: &mut RenderHandle<Buffered>,
+    drawable: Box<dyn Drawable>,
+) -> Result<ScreenKey, AppError> {
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(drawable)
+        .screen(screen)
+        .build_and_register(r)?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.content[0].map(|c| c.chr), Some('p'));
+    Ok(screen)
+}
+
+/// the object has been reported and is not drawn anymore
+fn assert_quarantined(
+    r: &RenderHandle<Buffered>,
+    errors: &Receiver<RenderError>,
+    panic: &str,
+) -> Result<(), AppError> {
+    let err = errors
+        .recv_timeout(Duration::from_secs(1))
+        .expect("panic should be reported");
+    assert_eq!(err.phase, RenderPhase::Quarantine);
+    assert!(matches!(
+        err.error,
+        AppError::Draw(DrawError::DrawablePanicked { ref message, .. }) if message == panic
+    ));
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.content[0], None);
+    Ok(())
+}
+
+#[test]
+fn panics_during_input_dispatch_get_quarantined() -> Result<(), AppError> {
+    let (handler, subscribers) = fake_event_source();
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1));
+    let mut r = start_renderer_with_event_handler(renderer, &handler);
+    let errors = r.errors()?;
+    add_drawn(&mut r, Box::new(PanickingOnInput))?;
+
+    press_key(&subscribers, 'a');
+    assert_quarantined(&r, &errors, "cannot handle keys")
+}
+
+#[test]
+fn panics_during_screen_selection_get_quarantined() -> Result<(), AppError> {
+    let (handler, _subscribers) = fake_event_source();
+    let (selections, select_rx) = mpsc::channel();
+    let (approvals, _approved) = mpsc::channel();
+    let mut renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((4, 1));
+    renderer.add_screen_select_handler(ScreenSelectHandler::new(select_rx, approvals));
+    let mut r = start_renderer_with_event_handler(renderer, &handler);
+    let errors = r.errors()?;
+    // selections only count, while the mouse is above a drawn object
+    let screen = add_drawn(&mut r, Box::new(PanickingOnInput))?;
+    selections
+        .send(ScreenSelectHMsg::Selection(TargetScreen::Screen(screen)))
+        .expect("renderer should hold the screen select handler");
+    assert_quarantined(&r, &errors, "cannot be selected")
+}
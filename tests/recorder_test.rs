@@ -0,0 +1,112 @@
+use ascii_assets::{AsciiVideo, TerminalChar};
+use common_stdx::{Point, Rect};
+use ltrender::display_screen::{AreaPoint, AreaRect};
+use ltrender::draw_object_builder::SpriteDrawableBuilder;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::recorder::RecordingArea;
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::terminal_buffer::standard_drawables::sprite_drawable::{AnimationInfo, FrameIdent};
+use ltrender::{DrawObjectBuilder, Renderer};
+
+fn frame_chars(video: &AsciiVideo, frame: usize) -> Vec<String> {
+    video
+        .get_frame(frame)
+        .expect("frame should exist")
+        .iter()
+        .map(|row| row.iter().map(|c| c.chr).collect())
+        .collect()
+}
+
+/// records a single `x` moving through a 3x2 screen in the top left corner
+fn record_moving_char(r: &mut RenderHandle<Buffered>) -> Result<AsciiVideo, AppError> {
+    let screen = r.create_screen(
+        AreaRect::FromPoints(AreaPoint::TopLeft, AreaPoint::Point(Point { x: 3, y: 2 })),
+        0,
+    )?;
+    r.start_recording(RecordingArea::Screen(screen))?;
+
+    let obj = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('x')))
+                .rect(Rect::from_coords(0, 0, 0, 0))
+        })?
+        .screen(screen)
+        .build_and_register(r)?;
+    r.render_frame()?;
+    r.move_drawable_to(obj, Point { x: 1, y: 0 })?;
+    r.render_frame()?;
+    r.move_drawable_to(obj, Point { x: 2, y: 1 })?;
+    r.render_frame()?;
+    r.stop_recording()
+}
+
+#[test]
+fn records_every_frame_of_a_screen() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((6, 3));
+    let mut r = start_renderer(renderer);
+    let video = record_moving_char(&mut r)?;
+
+    assert_eq!(video.size(), (3, 2, 3));
+    assert_eq!(frame_chars(&video, 0), vec!["x  ", "   "]);
+    assert_eq!(frame_chars(&video, 1), vec![" x ", "   "]);
+    assert_eq!(frame_chars(&video, 2), vec!["   ", "  x"]);
+
+    // the video survives a round trip through a file
+    let path = std::env::temp_dir().join(format!("ltrender_recording_{}.ascv", std::process::id()));
+    let path = path.to_str().expect("temp dir should be valid utf8");
+    video.write_to_file(path)?;
+    assert_eq!(AsciiVideo::read_from_file(path)?, video);
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn recordings_replay_as_sprites() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((6, 3));
+    let mut r = start_renderer(renderer);
+    let video = record_moving_char(&mut r)?;
+
+    let sprite = r.register_sprite(video)?;
+    let screen = r.create_screen(AreaRect::FullScreen, 1)?;
+    let drawable = SpriteDrawableBuilder::default()
+        .sprite_id(sprite)
+        .position((3, 0))
+        .animation_type(AnimationInfo::Image {
+            frame: FrameIdent::LastFrame,
+        })
+        .build()?;
+    DrawObjectBuilder::default()
+        .layer(0)
+        .screen(screen)
+        .drawable(drawable)
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    let chr = |x: usize, y: usize| frame.content[y * 6 + x].map(|c| c.chr);
+    assert_eq!(chr(2, 1), Some('x'));
+    assert_eq!(chr(5, 1), Some('x'));
+    Ok(())
+}
+
+#[test]
+fn stopping_without_recording_fails() {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((6, 3));
+    let r = start_renderer(renderer);
+    assert!(matches!(
+        r.stop_recording(),
+        Err(AppError::Draw(DrawError::NotRecording))
+    ));
+}
@@ -0,0 +1,79 @@
+use ascii_assets::{Color, TerminalChar};
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, SnapshotError};
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::testing::{Snapshot, assert_frame_matches, compare_frame};
+use ltrender::{DrawObjectBuilder, Renderer};
+
+const FIXTURE: &str = "tests/snapshots/colored_rect.snap";
+
+fn colored_rect() -> Result<RenderHandle<Buffered>, AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((6, 4));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let border = TerminalChar {
+        chr: '+',
+        fg_color: Some(Color::rgb(255, 0, 0)),
+        bg_color: None,
+    };
+    let fill = TerminalChar {
+        chr: 'o',
+        fg_color: Some(Color::rgb(0, 255, 0)),
+        bg_color: Some(Color::reset()),
+    };
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(border))
+                .fill_style(fill)
+                .rect(Rect::from_coords(0, 0, 4, 2))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    Ok(r)
+}
+
+#[test]
+fn frame_matches_fixture() -> Result<(), AppError> {
+    let r = colored_rect()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_frame_matches(&frame, FIXTURE);
+    Ok(())
+}
+
+#[test]
+fn text_format_round_trips() -> Result<(), AppError> {
+    let r = colored_rect()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    let snapshot = Snapshot::from(&frame);
+    let text = snapshot.to_text()?;
+    assert_eq!(Snapshot::parse(&text)?, snapshot);
+    Ok(())
+}
+
+#[test]
+fn mismatch_reports_differing_cells() -> Result<(), AppError> {
+    let r = colored_rect()?;
+    let mut frame = r.snapshot()?.expect("drawer should have flushed");
+    frame.content[0] = Some(TerminalChar::from_char('x'));
+
+    // never check_frame, it would overwrite the fixture with the broken frame when updating
+    match compare_frame(&frame, FIXTURE) {
+        Err(SnapshotError::Mismatch { report, .. }) => {
+            assert!(report.contains("1 cells differ"), "{}", report);
+            assert!(report.contains("(0, 0): expected '+'"), "{}", report);
+        }
+        other => panic!("expected a mismatch, got {:?}", other),
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::{DrawObjectBuilder, DrawObjectKey, ManualClock, Renderer, ScreenKey, SharedClock};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+fn add_rect(r: &mut RenderHandle<Buffered>, screen: ScreenKey) -> Result<DrawObjectKey, AppError> {
+    add_rect_with(r, screen, ObjectLifetime::ExplicitRemove)
+}
+
+fn add_rect_with(
+    r: &mut RenderHandle<Buffered>,
+    screen: ScreenKey,
+    lifetime: ObjectLifetime,
+) -> Result<DrawObjectKey, AppError> {
+    DrawObjectBuilder::default()
+        .add_lifetime(lifetime)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.fill_style(TerminalChar::from_char('.'))
+                .rect(Rect::from_coords(0, 0, 3, 3))
+        })?
+        .screen(screen)
+        .build_and_register(r)
+}
+
+#[test]
+fn destroyed_handles_are_rejected() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((20, 5));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+
+    let first = add_rect(&mut r, screen)?;
+    let second = add_rect(&mut r, screen)?;
+    r.destroy_drawable(first)?;
+
+    // the freed slot gets reused, but never with the same id
+    let third = add_rect(&mut r, screen)?;
+    assert_ne!(third, first);
+    assert_ne!(third, second);
+    assert_eq!(third.object_id.index, first.object_id.index);
+
+    let err = r.move_drawable_by(first, 1, 0).unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::Draw(DrawError::StaleDrawableHandle { .. })
+    ));
+    let err = r.render_drawable(first).unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::Draw(DrawError::StaleDrawableHandle { .. })
+    ));
+
+    // the other objects are untouched
+    r.move_drawable_by(second, 1, 0)?;
+    r.move_drawable_by(third, 1, 0)?;
+    Ok(())
+}
+
+#[test]
+fn destroyed_screens_are_rejected() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((20, 5));
+    let mut r = start_renderer(renderer);
+    let base = r.create_screen(AreaRect::FullScreen, 0)?;
+    let popup = r.create_screen(AreaRect::FullScreen, 1)?;
+    let base_rect = add_rect(&mut r, base)?;
+    let popup_rect = add_rect(&mut r, popup)?;
+    r.render_frame()?;
+
+    r.destroy_screen(popup)?;
+    r.render_frame()?;
+
+    let err = r.render_screen(popup).unwrap_err();
+    assert!(matches!(err, AppError::Draw(DrawError::StaleScreenKey(_))));
+    let err = r.destroy_screen(popup).unwrap_err();
+    assert!(matches!(err, AppError::Draw(DrawError::StaleScreenKey(_))));
+    assert!(r.move_drawable_by(popup_rect, 1, 0).is_err());
+
+    // a new screen never gets the key of the destroyed one
+    let new_screen = r.create_screen(AreaRect::FullScreen, 1)?;
+    assert_ne!(new_screen, popup);
+    r.move_drawable_by(base_rect, 1, 0)?;
+    Ok(())
+}
+
+#[test]
+fn removed_objects_keep_their_key() -> Result<(), AppError> {
+    let clock = Arc::new(ManualClock::default());
+    let mut r = Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((20, 5));
+    r.set_clock(SharedClock::from_arc(clock.clone()));
+    let screen = r.create_screen(AreaRect::FullScreen, 0);
+    let mut add = |lifetime| -> Result<DrawObjectKey, AppError> {
+        let obj = DrawObjectBuilder::default()
+            .add_lifetime(lifetime)
+            .layer(0)
+            .rect_drawable(|r| {
+                r.fill_style(TerminalChar::from_char('.'))
+                    .rect(Rect::from_coords(0, 0, 3, 3))
+            })?
+            .build()?;
+        Ok(r.register_drawable(screen, obj)?)
+    };
+    let removed = add(ObjectLifetime::ExplicitRemove)?;
+    let once = add(ObjectLifetime::RemoveNextFrame)?;
+    let timed = add(ObjectLifetime::ForTime(Duration::from_secs(1)))?;
+
+    r.explicit_remove_drawable(&removed)?;
+    r.remove_all_framebased_objects()?;
+    clock.advance(Duration::from_secs(2));
+    r.check_if_object_lifetime_ended()?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert!(frame.content.iter().all(Option::is_none));
+
+    // hidden objects can be shown again, until they are destroyed
+    for key in [removed, once, timed] {
+        r.render_drawable(key)?;
+        r.render_frame()?;
+        let frame = r.snapshot()?.expect("drawer should have flushed");
+        assert!(frame.content.iter().any(Option::is_some));
+        r.destroy_drawable(key)?;
+        let err = r.render_drawable(key).unwrap_err();
+        assert!(matches!(err, DrawError::StaleDrawableHandle { .. }));
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use std::sync::atomic::Ordering;
+
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    DefaultScreenBuffer, SinkFailure, TeeCellDrawer, TestCellDrawer,
+};
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, Renderer};
+
+use common::BrokenDrawer;
+
+#[test]
+fn every_sink_receives_the_frames() -> Result<(), AppError> {
+    let first = TestCellDrawer::init();
+    let second = TestCellDrawer::init();
+    let first_capture = first.capture().expect("test drawer captures");
+    let second_capture = second.capture().expect("test drawer captures");
+    let broken = BrokenDrawer::default();
+    let broken_batches = broken.batches.clone();
+
+    let tee = TeeCellDrawer::new()
+        .sink(first)
+        .sink_with(broken, SinkFailure::Detach)
+        .sink(second);
+    assert_eq!(tee.active_sinks(), 3);
+    let buffer = DefaultScreenBuffer::with_drawer((4, 2), tee);
+    let renderer = Renderer::<_, Buffered>::create_renderer_with_buffer((4, 2), buffer);
+    let mut r = start_renderer(renderer);
+
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let rect = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('#')))
+                .rect(Rect::from_coords(0, 0, 4, 2))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    // the snapshot comes from the first sink
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(frame.content[0].map(|c| c.chr), Some('#'));
+    let drawn = broken_batches.load(Ordering::SeqCst);
+    assert!(drawn > 0);
+
+    // the broken sink got detached after its first flush, the others keep drawing
+    r.explicit_remove_drawable(&rect)?;
+    r.render_frame()?;
+    r.snapshot()?;
+    assert_eq!(broken_batches.load(Ordering::SeqCst), drawn);
+    for capture in [first_capture, second_capture] {
+        let history = capture.history();
+        let [.., drawn, removed] = history.as_slice() else {
+            panic!("every sink should have received both frames");
+        };
+        assert_eq!(drawn.content[0].map(|c| c.chr), Some('#'));
+        assert_eq!(removed.content[0], None);
+    }
+    Ok(())
+}
+
+#[test]
+fn propagated_failures_do_not_stop_other_sinks() {
+    let mut tee = TeeCellDrawer::new()
+        .sink(BrokenDrawer::default())
+        .sink_with(BrokenDrawer::default(), SinkFailure::Log)
+        .sink(TestCellDrawer::init());
+    let capture = tee.capture().expect("test drawer captures");
+    assert!(tee.flush().is_err());
+    assert_eq!(tee.active_sinks(), 3);
+    assert_eq!(capture.history().len(), 1);
+}
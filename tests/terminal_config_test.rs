@@ -0,0 +1,67 @@
+use ltrender::TerminalConfig;
+use ltrender::rendering::panic_isolation::catch_panic;
+
+fn setup(config: &TerminalConfig) -> String {
+    let mut out = Vec::new();
+    config.write_setup(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn teardown(config: &TerminalConfig) -> String {
+    let mut out = Vec::new();
+    config.write_teardown(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn only_selected_features_get_enabled_and_restored() {
+    let config = TerminalConfig::none()
+        .bracketed_paste(true)
+        .focus_reporting(true);
+    assert_eq!(setup(&config), "\x1b[?2004h\x1b[?1004h");
+    assert_eq!(teardown(&config), "\x1b[?1004l\x1b[?2004l");
+
+    let none = TerminalConfig::none();
+    assert_eq!(setup(&none), "");
+    assert_eq!(teardown(&none), "");
+}
+
+#[test]
+fn default_takes_over_the_whole_screen() {
+    let config = TerminalConfig::default();
+    assert!(config.raw_mode);
+    let setup = setup(&config);
+    assert!(setup.starts_with("\x1b[?1049h"));
+    assert!(setup.contains("\x1b[?25l"));
+    assert!(!setup.contains("\x1b[?2004h"));
+
+    let teardown = teardown(&config);
+    assert!(teardown.ends_with("\x1b[?1049l"));
+    assert!(teardown.contains("\x1b[?25h"));
+
+    let without_screen = TerminalConfig::default().alternate_screen(false);
+    assert!(!self::setup(&without_screen).contains("\x1b[?1049h"));
+}
+
+#[test]
+fn guard_restores_only_once() {
+    let guard = TerminalConfig::none().enter().unwrap();
+    assert!(!guard.config().raw_mode);
+    guard.restore().unwrap();
+    guard.restore().unwrap();
+}
+
+#[test]
+fn isolated_panics_keep_the_terminal() {
+    let guard = TerminalConfig::none()
+        .restore_on_panic(true)
+        .enter()
+        .unwrap();
+    let caught = catch_panic(|| panic!("drawable broke"));
+    assert_eq!(caught, Err(String::from("drawable broke")));
+    assert!(!guard.is_restored());
+
+    // any other panic still restores the terminal
+    assert!(std::panic::catch_unwind(|| panic!("app broke")).is_err());
+    assert!(guard.is_restored());
+}
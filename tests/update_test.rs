@@ -0,0 +1,54 @@
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Instant;
+use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::TestCellDrawer;
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::{BorderStyle, RectDrawable};
+use ltrender::terminal_buffer::standard_drawables::text_drawable::TextDrawable;
+use ltrender::{DrawObjectBuilder, Renderer};
+
+fn first_row(r: &RenderHandle<Instant>) -> String {
+    let data = r.snapshot().unwrap().expect("drawer should have flushed");
+    data.content[..data.size.0 as usize]
+        .iter()
+        .map(|c| c.map(|c| c.chr).unwrap_or(' '))
+        .collect()
+}
+
+#[test]
+fn update_mutates_the_concrete_drawable() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Instant>::create_renderer((6, 1));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let rect = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('#')))
+                .rect(Rect::from_coords(0, 0, 2, 0))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    assert_eq!(first_row(&r), "###   ");
+
+    r.update::<RectDrawable, _>(rect, |rect| {
+        rect.border_style = BorderStyle::AllRound(TerminalChar::from_char('*'));
+        rect.rect.p2.x = 3;
+    })?;
+    assert_eq!(first_row(&r), "****  ");
+
+    let err = r.update::<TextDrawable, _>(rect, |_| {}).unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::Draw(DrawError::WrongDrawableType { .. })
+    ));
+    // a failed update leaves the drawable as it was
+    assert_eq!(first_row(&r), "****  ");
+    Ok(())
+}
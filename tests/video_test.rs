@@ -7,6 +7,7 @@ use ltrender::display_screen::{AreaPoint, AreaRect};
 use ltrender::draw_object_builder::SpriteDrawableBuilder;
 use ltrender::drawable_register::ObjectLifetime;
 use ltrender::error::{AppError, DrawError};
+use ltrender::rendering::render_handle::RenderHandle;
 use ltrender::rendering::render_thread::start_renderer;
 use ltrender::rendering::renderer::Buffered;
 use ltrender::terminal_buffer::buffer_and_celldrawer::DefaultScreenBuffer;
@@ -16,11 +17,10 @@ use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
 use ltrender::terminal_buffer::standard_drawables::sprite_drawable::{
     AnimationInfo, FrameIdent, VideoLoopType, VideoSpeed,
 };
-use ltrender::{DrawObjectBuilder, Renderer, get_test_data};
+use ltrender::{DrawObjectBuilder, Renderer};
 
 use std::fs::File;
 use std::io::Write;
-use std::time::Duration;
 use std::{panic, thread};
 
 fn create_first_frame() -> Vec<TerminalChar> {
@@ -89,7 +89,7 @@ fn video_render_test() -> Result<(), AppError> {
     r.set_update_interval(16)?;
 
     let screen = r.create_screen(AreaRect::FullScreen, 5)?;
-    expect_no_data();
+    expect_no_data(&r);
 
     let obj_id =
         if let Ok(sprite) = r.register_sprite_from_source("./assets/debugging/test_video.ascv") {
@@ -114,29 +114,29 @@ fn video_render_test() -> Result<(), AppError> {
             panic!("failed to register sprite from source")
         };
 
-    expect_no_data();
+    expect_no_data(&r);
 
     // making sure flush hasnt been called
-    test_if_screen_doesnt_exit();
+    test_if_screen_doesnt_exit(&r);
 
-    expect_no_data();
+    expect_no_data(&r);
 
     // rendering and expecting to see the video
     r.render_frame()?;
-    test_if_video_exist(Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
 
     // video should be removed, only after render frame has been called
     r.explicit_remove_drawable(&obj_id)?;
-    test_if_video_exist(Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
     r.render_frame()?;
 
     // expecting video to be removed
-    test_if_eq_at_pos(Point::from((0, 0)), None);
+    test_if_eq_at_pos(&r, Point::from((0, 0)), None);
 
     // rendering again
     r.render_drawable(obj_id)?;
     r.render_frame()?;
-    test_if_video_exist(Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
 
     // changing the screen area, should only create change on the screen, after render frame has been called
     r.change_screen_area(
@@ -146,10 +146,10 @@ fn video_render_test() -> Result<(), AppError> {
             AreaPoint::BottomRight,
         ),
     )?;
-    test_if_video_exist(Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 0, y: 0 }, ref_frame.clone(), sprite_size);
     // video should now be at 1,1
     r.render_frame()?;
-    test_if_video_exist(Point { x: 1, y: 1 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 1, y: 1 }, ref_frame.clone(), sprite_size);
 
     //now adding a rect on a screen below the video, then moving it above
     // using 1,1 as position, since the video is also there
@@ -177,50 +177,30 @@ fn video_render_test() -> Result<(), AppError> {
         .build_and_register(&mut r)?;
 
     // now the video should still be visible at 1,1
-    test_if_video_exist(Point { x: 1, y: 1 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 1, y: 1 }, ref_frame.clone(), sprite_size);
     r.render_frame()?;
     // and now still
-    test_if_video_exist(Point { x: 1, y: 1 }, ref_frame.clone(), sprite_size);
+    test_if_video_exist(&r, Point { x: 1, y: 1 }, ref_frame.clone(), sprite_size);
     // rect should be visible at 0,0
-    test_if_eq_at_pos(Point { x: 0, y: 0 }, Some(border_char));
-    test_if_eq_at_pos(Point { x: 0, y: 1 }, Some(border_char));
-    test_if_eq_at_pos(Point { x: 1, y: 0 }, Some(border_char));
-    test_if_eq_at_pos(Point { x: 0, y: 2 }, None);
+    test_if_eq_at_pos(&r, Point { x: 0, y: 0 }, Some(border_char));
+    test_if_eq_at_pos(&r, Point { x: 0, y: 1 }, Some(border_char));
+    test_if_eq_at_pos(&r, Point { x: 1, y: 0 }, Some(border_char));
+    test_if_eq_at_pos(&r, Point { x: 0, y: 2 }, None);
 
     // changing the layer, now the rect should be above
     r.change_screen_layer(rect_screen, 10)?;
     r.render_frame()?;
-    test_if_eq_at_pos(Point { x: 1, y: 1 }, Some(border_char));
+    test_if_eq_at_pos(&r, Point { x: 1, y: 1 }, Some(border_char));
 
     ltrender::restore_terminal()?;
     Ok(())
 }
 
+/// the content of the last flush, after the drawer has handled all pending commands
 #[track_caller]
-fn wait_for_test_data(timeout_ms: u64) -> Option<TerminalContentInformation> {
-    let start = std::time::Instant::now();
-    let timeout = Duration::from_millis(timeout_ms);
-
-    let baseline = get_test_data();
-    while start.elapsed() < timeout {
-        if let Some(current) = get_test_data() {
-            if baseline.is_none() {
-                return Some(current);
-            }
-
-            let base = baseline.as_ref().unwrap();
-            let changed_by_cmds = current.amount_of_draw_commands != base.amount_of_draw_commands;
-            let changed_by_content = current.content != base.content;
-
-            if changed_by_cmds || changed_by_content {
-                return Some(current);
-            }
-        }
-
-        thread::sleep(Duration::from_millis(5));
-    }
-
-    get_test_data().or(baseline)
+fn snapshot(r: &RenderHandle<Buffered>) -> Option<TerminalContentInformation> {
+    r.snapshot()
+        .expect("failed to get a snapshot from the renderer")
 }
 
 fn dump_test_data_brief(opt: Option<TerminalContentInformation>) -> String {
@@ -252,7 +232,7 @@ fn dump_test_data_brief(opt: Option<TerminalContentInformation>) -> String {
                 for col in 0..max_cols {
                     let idx = row * width + col;
                     if idx < d.content.len() {
-                        cols.push(format!("{:?}", &d.content[idx]));
+                        cols.push(format!("{:?}", d.content[idx]));
                     } else {
                         cols.push(" ".to_string());
                     }
@@ -272,9 +252,9 @@ fn dump_test_data_brief(opt: Option<TerminalContentInformation>) -> String {
     }
 }
 #[track_caller]
-fn test_if_eq_at_pos(pos: Point<u16>, term_char: Option<TerminalChar>) {
+fn test_if_eq_at_pos(r: &RenderHandle<Buffered>, pos: Point<u16>, term_char: Option<TerminalChar>) {
     info!("testing if {:?} exists at position {:?}", term_char, pos);
-    let opt_data = wait_for_test_data(10).or_else(get_test_data);
+    let opt_data = snapshot(r);
 
     if let Some(data) = opt_data {
         if data.content.is_empty() {
@@ -299,21 +279,21 @@ fn test_if_eq_at_pos(pos: Point<u16>, term_char: Option<TerminalChar>) {
         let actual = &data.content[idx];
         let info = format!(
             "mismatch at pos {:?}: expected={:?} found={:?}",
-            pos, &term_char, &actual
+            pos, term_char, actual
         );
         if actual != &term_char {
             panic!("{}{}{}", info, "  ", dump_test_data_brief(Some(data)))
         }
     } else {
         panic!(
-            "timed out waiting for TerminalContentInformation. snapshot:{}",
-            dump_test_data_brief(get_test_data())
+            "no TerminalContentInformation has been flushed. snapshot:{}",
+            dump_test_data_brief(snapshot(r))
         );
     }
 }
 #[track_caller]
-fn expect_no_data() {
-    if let Some(data) = wait_for_test_data(10) {
+fn expect_no_data(r: &RenderHandle<Buffered>) {
+    if let Some(data) = snapshot(r) {
         panic!(
             "there should be no TerminalContentInformation here, but got: {}",
             dump_test_data_brief(Some(data))
@@ -323,11 +303,12 @@ fn expect_no_data() {
 
 #[track_caller]
 fn test_if_video_exist(
+    r: &RenderHandle<Buffered>,
     point: Point<u16>,
     expected_frame: Vec<TerminalChar>,
     size_of_frame: (u16, u16),
 ) {
-    let opt_data = wait_for_test_data(10);
+    let opt_data = snapshot(r);
     if let Some(_data) = opt_data {
         let expected_len = size_of_frame.0 * size_of_frame.1;
         if expected_len as usize != expected_frame.len() {
@@ -351,20 +332,20 @@ fn test_if_video_exist(
                     .get(frame_idx as usize)
                     .copied()
                     .expect("expected_frame indexing out of bounds");
-                test_if_eq_at_pos(screen_pos, Some(expected_char));
+                test_if_eq_at_pos(r, screen_pos, Some(expected_char));
             }
         }
     } else {
         panic!(
-            "timed out waiting for TerminalContentInformation while expecting video. Current snapshot: {}",
-            dump_test_data_brief(get_test_data())
+            "no TerminalContentInformation has been flushed while expecting video. Current snapshot: {}",
+            dump_test_data_brief(snapshot(r))
         );
     }
 }
 
 #[track_caller]
-fn test_if_screen_doesnt_exit() {
-    if let Some(data) = wait_for_test_data(10)
+fn test_if_screen_doesnt_exit(r: &RenderHandle<Buffered>) {
+    if let Some(data) = snapshot(r)
         && !data.content.is_empty()
     {
         panic!(
@@ -0,0 +1,146 @@
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::drawable_traits::basic_draw_creator::BasicDrawCreator;
+use ltrender::error::AppError;
+use ltrender::rendering::render_handle::RenderHandle;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::crossterm_celldrawer::encode_batch;
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    BatchDrawInfo, BatchSegment, CrosstermOptions, DefaultScreenBuffer, TestCellDrawer,
+};
+use ltrender::terminal_buffer::grapheme::{Grapheme, char_width, str_width, text_width};
+use ltrender::terminal_buffer::standard_drawables::text_drawable::{
+    LineInfo, TextAlignment, TextDrawable, TextStyle,
+};
+use ltrender::{CellAttributes, DrawObjectBuilder, DrawObjectKey, Renderer};
+
+fn text_drawable(text: &str, width: i32) -> TextDrawable {
+    TextDrawable {
+        area: Rect::from_coords(0, 0, width, 1),
+        lines: vec![LineInfo {
+            text: text.to_string(),
+            spans: Vec::new(),
+            alignment: TextAlignment::Left,
+            default_style: TextStyle::default(),
+        }],
+        wrapping: false,
+        scroll_y: 0,
+    }
+}
+
+fn render_text(
+    text: &str,
+    width: u16,
+) -> Result<(RenderHandle<Buffered>, DrawObjectKey), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((width, 1));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    let key = DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(Box::new(text_drawable(text, width as i32)))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    Ok((r, key))
+}
+
+fn glyphs(content: &[Option<TerminalChar>]) -> String {
+    content.iter().map(|c| c.map_or('_', |c| c.chr)).collect()
+}
+
+#[test]
+fn measures_display_width() {
+    assert_eq!(char_width('a'), 1);
+    assert_eq!(char_width('中'), 2);
+    assert_eq!(char_width('\u{301}'), 1);
+    assert_eq!(str_width("e\u{301}"), 1);
+    assert_eq!(str_width("👍🏽"), 2);
+    assert_eq!(text_width("a中e\u{301}"), 4);
+
+    let cluster = Grapheme::new("e\u{301}");
+    assert_eq!(cluster.base(), 'e');
+    assert_eq!(cluster.as_str(), "e\u{301}");
+    // clusters longer than the inline storage are cut at a char boundary
+    let long = "a".to_string() + &"\u{301}".repeat(20);
+    assert!(Grapheme::new(&long).as_str().len() <= 31);
+}
+
+#[test]
+fn draw_text_advances_by_width() {
+    let mut creator = BasicDrawCreator::new();
+    creator.draw_text((0, 0), "中e\u{301}x", None);
+    let mut draws = creator.dump_draws();
+    draws.sort_by_key(|d| d.pos.x);
+
+    let placed: Vec<(i32, char)> = draws.iter().map(|d| (d.pos.x, d.chr.chr)).collect();
+    assert_eq!(placed, vec![(0, '中'), (2, 'e'), (3, 'x')]);
+    assert_eq!(
+        draws[1].cluster.map(|c| c.to_string()),
+        Some("e\u{301}".to_string())
+    );
+    assert_eq!(draws[2].cluster, None);
+}
+
+#[test]
+fn wide_chars_cover_two_cells() -> Result<(), AppError> {
+    let (r, _) = render_text("a中b", 6)?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(glyphs(&frame.content), "a中_b__");
+    assert_eq!(glyphs(&r.composed_content()?.content), "a中_b__");
+    Ok(())
+}
+
+#[test]
+fn wide_char_in_last_column_is_blanked() -> Result<(), AppError> {
+    let renderer =
+        Renderer::<DefaultScreenBuffer<TestCellDrawer>, Buffered>::create_renderer((3, 1));
+    let mut r = start_renderer(renderer);
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    // the text area reaches past the terminal, so the wide char only has one cell left
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .drawable(Box::new(text_drawable("ab中", 4)))
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(glyphs(&frame.content), "ab_");
+    // the composed content agrees with the drawer on the char as well as its width
+    assert_eq!(r.composed_content()?.content, frame.content);
+    Ok(())
+}
+
+#[test]
+fn narrow_text_replacing_wide_redraws_the_covered_cell() -> Result<(), AppError> {
+    let (r, key) = render_text("中x", 4)?;
+    r.update::<TextDrawable, _>(key, |t| t.lines[0].text = String::from("ab"))?;
+    r.render_frame()?;
+    let frame = r.snapshot()?.expect("drawer should have flushed");
+    assert_eq!(glyphs(&frame.content), "ab__");
+    Ok(())
+}
+
+#[test]
+fn cursor_is_placed_after_wide_clusters() {
+    let batch = BatchDrawInfo {
+        start_x: 2,
+        y: 0,
+        segments: vec![BatchSegment {
+            text: String::from("中e\u{301}b"),
+            fg_color: None,
+            bg_color: None,
+            attrs: CellAttributes::NONE,
+        }],
+    };
+    let out = encode_batch(&batch, &CrosstermOptions::default());
+    assert_eq!(
+        out,
+        "\x1b[1;3H\x1b[39m\x1b[49m中\x1b[5Ge\u{301}\x1b[6Gb\x1b[0m"
+    );
+}
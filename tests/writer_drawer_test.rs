@@ -0,0 +1,90 @@
+mod common;
+
+use ascii_assets::TerminalChar;
+use common_stdx::Rect;
+use ltrender::CellAttributes;
+use ltrender::display_screen::AreaRect;
+use ltrender::drawable_register::ObjectLifetime;
+use ltrender::error::AppError;
+use ltrender::rendering::render_thread::start_renderer;
+use ltrender::rendering::renderer::Buffered;
+use ltrender::terminal_buffer::CellDrawer;
+use ltrender::terminal_buffer::buffer_and_celldrawer::standard_celldrawer::crossterm_celldrawer::{
+    BEGIN_SYNCHRONIZED_UPDATE, END_SYNCHRONIZED_UPDATE,
+};
+use ltrender::terminal_buffer::buffer_and_celldrawer::{
+    BatchDrawInfo, BatchSegment, CrosstermCellDrawer, CrosstermOptions, DefaultScreenBuffer,
+};
+use ltrender::terminal_buffer::standard_drawables::rect_drawable::BorderStyle;
+use ltrender::{DrawObjectBuilder, Renderer};
+
+use common::SharedWriter;
+
+#[test]
+fn frames_are_written_into_the_writer() -> Result<(), AppError> {
+    let out = SharedWriter::default();
+    let options = CrosstermOptions::default();
+    let drawer = CrosstermCellDrawer::with_writer(out.clone(), options);
+    let buffer = DefaultScreenBuffer::with_drawer((4, 2), drawer);
+    let renderer = Renderer::<_, Buffered>::create_renderer_with_buffer((4, 2), buffer);
+    let mut r = start_renderer(renderer);
+
+    let screen = r.create_screen(AreaRect::FullScreen, 0)?;
+    DrawObjectBuilder::default()
+        .add_lifetime(ObjectLifetime::ExplicitRemove)
+        .layer(0)
+        .rect_drawable(|r| {
+            r.border_style(BorderStyle::AllRound(TerminalChar::from_char('#')))
+                .rect(Rect::from_coords(0, 0, 4, 2))
+        })?
+        .screen(screen)
+        .build_and_register(&mut r)?;
+    r.set_cursor_position(screen, (1, 1))?;
+    r.show_cursor()?;
+    r.render_frame()?;
+    r.snapshot()?;
+
+    let frame = out.take();
+    // the whole frame is one synchronized update, that puts the cursor back in the end
+    let (_, update) = frame
+        .split_once(BEGIN_SYNCHRONIZED_UPDATE)
+        .expect("frame should start a synchronized update");
+    assert!(update.contains("####"));
+    assert!(update.ends_with(&format!("\x1b[2;2H\x1b[?25h{END_SYNCHRONIZED_UPDATE}")));
+
+    // nothing changed, so nothing gets written
+    r.render_frame()?;
+    r.snapshot()?;
+    assert_eq!(out.take(), "");
+    Ok(())
+}
+
+fn drawn_flush(options: CrosstermOptions) -> String {
+    let out = SharedWriter::default();
+    let mut drawer = CrosstermCellDrawer::with_writer(out.clone(), options);
+    let batch = BatchDrawInfo {
+        start_x: 0,
+        y: 0,
+        segments: vec![BatchSegment {
+            text: String::from("ab"),
+            fg_color: None,
+            bg_color: None,
+            attrs: CellAttributes::NONE,
+        }],
+    };
+    drawer.set_string(batch, (4, 1));
+    drawer.flush().expect("writing into memory never fails");
+    out.take()
+}
+
+#[test]
+fn synchronized_output_wraps_the_whole_flush() {
+    let plain = drawn_flush(CrosstermOptions::default().synchronized_output(false));
+    assert_eq!(plain, "\x1b[1;1H\x1b[39m\x1b[49mab\x1b[0m\x1b[?25l");
+
+    let synchronized = drawn_flush(CrosstermOptions::default());
+    assert_eq!(
+        synchronized,
+        format!("{BEGIN_SYNCHRONIZED_UPDATE}{plain}{END_SYNCHRONIZED_UPDATE}")
+    );
+}